@@ -2,8 +2,18 @@
 
 (
 	nasm -felf64 out.asm &&
-	gcc -no-pie -fno-pie out.o &&
-	rm ./out.o &&
-	./a.out "$@" &&
-	rm ./a.out
-) || rm ./a.out
\ No newline at end of file
+	if [ -n "$DRIVER" ]; then
+		# Libraries are linked into the C program given in DRIVER
+		gcc -shared out.o -o librost.so &&
+		gcc "$DRIVER" -L. -lrost -Wl,-rpath,'$ORIGIN' -o a.out
+	else
+		gcc -no-pie -fno-pie out.o
+	fi &&
+	rm ./out.o
+) || { rm -f ./a.out ./librost.so; exit 1; }
+
+# Keep the exit status of the program
+./a.out "$@"
+status=$?
+rm -f ./a.out ./librost.so
+exit $status
\ No newline at end of file
@@ -13,9 +13,13 @@ for file in ./tests/*.in; do
 
 	echo "[Running test ${file}]" | tee -a tests.log
 	name=$(echo $file | sed 's/.*\///;s/\.in//')
-	./target/debug/rost ./tests/${name}.in "$@" >> tests.log || echo "Compiler error for test ${name}, check logs" | tee -a tests.log || exit
-	output=$(docker run -v $(PWD):/app rost first second)
+	flags=$(cat ./tests/${name}.flags 2> /dev/null) # extra compiler flags for the test
+	./target/debug/rost ./tests/${name}.in $flags "$@" >> tests.log || echo "Compiler error for test ${name}, check logs" | tee -a tests.log || exit
+	driver=$(ls ./tests/${name}.c 2> /dev/null) # C program using the test as a library
+	output=$(docker run -e DRIVER=${driver} -v $(PWD):/app rost first second 2>&1)
+	status=$?
 	expected=$(cat ./tests/${name}.out)
+	expected_status=$(cat ./tests/${name}.status 2> /dev/null || echo 0) # exit status of the program
 	if [ "$output" != "$expected" ]; then
 		echo "Got unexpected output for test ${name}. Got:" | tee -a tests.log
 		echo "------------" | tee -a tests.log
@@ -27,6 +31,10 @@ for file in ./tests/*.in; do
 		echo "------------" | tee -a tests.log
 		exit
 	fi
+	if [ "$status" != "$expected_status" ]; then
+		echo "Got exit status ${status} for test ${name}, expected ${expected_status}" | tee -a tests.log
+		exit
+	fi
 	echo "[Tests pass]" | tee -a tests.log
 	echo "==========" >> tests.log
 done
@@ -35,7 +43,8 @@ echo "[Running error tests]" | tee -a tests.log
 for file in ./tests/error_*.in; do
 	echo "[Running test ${file}]" | tee -a tests.log
 	name=$(echo $file | sed 's/.*\///;s/\.in//')
-	output=$(./target/debug/rost ./tests/${name}.in "$@" | sed -E "s/[[:cntrl:]]\[[0-9]{1,3}m//g") # remove colors from output
+	flags=$(cat ./tests/${name}.flags 2> /dev/null)
+	output=$(./target/debug/rost ./tests/${name}.in $flags "$@" | sed -E "s/[[:cntrl:]]\[[0-9]{1,3}m//g") # remove colors from output
 	expected=$(cat ./tests/${name}.out)
 	if [ "$output" != "$expected" ]; then
 		echo "Got unexpected output for test ${name}. Got:" | tee -a tests.log
@@ -22,14 +22,42 @@ impl Program {
             todo!("Cannot index using a non integer value");
         }
 
-        let pointer_type = self.infer_type(&index.left)?;
-        let VariableType::Pointer(value_type) = pointer_type else {
-            todo!("Cannot index into non pointer value")
+        let left_type = self.infer_type(&index.left)?;
+        let (base, value_type) = match left_type.clone() {
+            VariableType::Pointer(value_type) => {
+                (self.handle_dereferenced_pointer(&index.left)?, value_type)
+            }
+            // Arrays are indexed from the address of their first element
+            VariableType::Array(value_type, _) => (self.handle_ref(&index.left)?, value_type),
+            VariableType::Slice(value_type) => {
+                (self.handle_slice_pointer(&index.left)?, value_type)
+            }
+            _ => todo!("Cannot index into non pointer value"),
         };
 
-        let builder = Builder::new()
-            .append(self.handle_expression(&index.left)?)
-            .append(self.handle_expression(&index.index)?)
+        let mut builder = Builder::new()
+            .append(base)
+            .append(self.handle_expression(&index.index)?);
+
+        if self.checked {
+            let length = match left_type {
+                VariableType::Array(_, length) => Some(Builder::new().push(Procedure::new(
+                    expression.pos.clone(),
+                    ProcedureKind::Push(OperandValue::Int(length as i32)),
+                ))),
+                VariableType::Slice(_) => Some(self.handle_slice_length(&index.left)?),
+                _ => None,
+            };
+
+            if let Some(length) = length {
+                builder = builder.append(length).push(Procedure::new(
+                    expression.pos.clone(),
+                    ProcedureKind::BoundsCheck,
+                ));
+            }
+        }
+
+        let builder = builder
             .push(Procedure::new(
                 expression.pos.clone(),
                 ProcedureKind::Push(OperandValue::Int(Self::get_type_size(&value_type) as i32)),
@@ -51,9 +79,10 @@ impl Program {
         expression: &Expression,
         index: &ArrayIndex,
     ) -> Result<Builder, CompilerError> {
+        let typ = self.infer_type(expression)?;
         let builder = self
             .handle_array_index_without_deref(expression, index)?
-            .push(Procedure::new(expression.pos.clone(), ProcedureKind::Deref));
+            .append(Self::get_load(&typ, &expression.pos));
 
         Ok(builder)
     }
@@ -0,0 +1,138 @@
+use std::ops::Range;
+
+use crate::parser::definition::{ArrayLiteral, Expression};
+
+use super::{
+    builder::Builder,
+    definition::{Arithmetic, Extend, OperandValue, Procedure, ProcedureKind, RegisterSize},
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::VariableType,
+};
+
+impl Program {
+    /// The elements in the order they are pushed,
+    /// the first element ends up at the lowest address.
+    pub fn get_array_literal_elements(array: &ArrayLiteral) -> Vec<&Expression> {
+        match array {
+            ArrayLiteral::List(elements) => elements.iter().collect(),
+            ArrayLiteral::Repeat(value, length) => vec![value.as_ref(); *length],
+        }
+    }
+
+    pub fn infer_array_literal_type(
+        &self,
+        expression: &Expression,
+        array: &ArrayLiteral,
+    ) -> Result<VariableType, CompilerError> {
+        let (first, length) = match array {
+            ArrayLiteral::List(elements) => (elements.first(), elements.len()),
+            ArrayLiteral::Repeat(value, length) => (Some(value.as_ref()), *length),
+        };
+
+        let Some(first) = first else {
+            return Err(CompilerError::new(
+                expression.pos.clone(),
+                CompilerErrorKind::EmptyArray,
+            ));
+        };
+
+        let element_type = self.infer_type(first)?;
+
+        if let ArrayLiteral::List(elements) = array {
+            for element in elements.iter().skip(1) {
+                let typ = self.infer_type(element)?;
+
+                if typ != element_type {
+                    return Err(CompilerError::new(
+                        element.pos.clone(),
+                        CompilerErrorKind::WrongType {
+                            got: typ,
+                            expected: element_type,
+                            expected_alias: None,
+                        },
+                    ));
+                }
+            }
+        }
+
+        Ok(VariableType::Array(Box::new(element_type), length))
+    }
+
+    /// Pushes every element, a repeated value is evaluated once per element.
+    /// Elements smaller than a slot are packed together,
+    /// so the pushed value has the same layout as the array in memory.
+    pub fn handle_array_literal(
+        &mut self,
+        expression: &Expression,
+        array: &ArrayLiteral,
+    ) -> Result<Builder, CompilerError> {
+        let VariableType::Array(element_type, _) =
+            self.infer_array_literal_type(expression, array)?
+        else {
+            unreachable!()
+        };
+
+        let element_size = Self::get_type_size(&element_type);
+        let elements = Self::get_array_literal_elements(array);
+
+        let mut builder = Builder::new();
+
+        for slot in elements.chunks((8 / element_size).max(1)) {
+            for (i, element) in slot.iter().enumerate() {
+                builder = builder.append(Self::get_packed_value(
+                    self.handle_expression(element)?,
+                    &element.pos,
+                    element_size,
+                    i * element_size,
+                ));
+
+                if i > 0 {
+                    builder = builder.push(Procedure::new(
+                        element.pos.clone(),
+                        ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+                    ));
+                }
+            }
+        }
+
+        Ok(builder)
+    }
+
+    /// Moves the pushed value of `size` bytes to the byte offset `within_slot` of its slot.
+    /// Values smaller than a slot have their unused bytes cleared,
+    /// so they can be added to the other values sharing the slot.
+    pub fn get_packed_value(
+        value: Builder,
+        pos: &Range<usize>,
+        size: usize,
+        within_slot: usize,
+    ) -> Builder {
+        let mut builder = value;
+
+        if matches!(size, 1 | 2 | 4) {
+            builder = builder.push(Procedure::new(
+                pos.clone(),
+                ProcedureKind::Extend(Extend {
+                    size: RegisterSize::get_register(size),
+                    signed: false,
+                }),
+            ));
+        }
+
+        // Shift the value into its byte offset within the slot
+        for _ in 0..within_slot {
+            builder = builder
+                .push(Procedure::new(
+                    pos.clone(),
+                    ProcedureKind::Push(OperandValue::Int(256)),
+                ))
+                .push(Procedure::new(
+                    pos.clone(),
+                    ProcedureKind::Arithmetic(Arithmetic::Multiply, RegisterSize::B64),
+                ));
+        }
+
+        builder
+    }
+}
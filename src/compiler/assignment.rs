@@ -1,59 +1,187 @@
 use crate::{
     lexer::Keyword,
-    parser::definition::{ExpressionKind, Primary, Unary, VariableAssignment, VariableDeclaration},
+    parser::definition::{
+        Expression, ExpressionKind, Primary, Unary, VariableAssignment, VariableDeclaration,
+    },
 };
 
 use super::{
     builder::Builder,
-    definition::{Assign, Procedure, ProcedureKind},
+    definition::{Assign, GlobalData, Procedure, ProcedureKind},
     error::{CompilerError, CompilerErrorKind},
     program::Program,
-    scope::variable::{Variable, VariableLocation, VariableType},
+    scope::{
+        variable::{Variable, VariableLocation, VariableType},
+        ProgramScope,
+    },
 };
 
 impl Program {
+    /// Errors if the place can not be assigned to.
+    /// Variables have to be declared `mut`, unless they are declared without a value
+    /// and no path to the assignment, including earlier iterations of a loop, assigns them,
+    /// while writing through a pointer is allowed as long as it doesn't point to const.
+    fn check_mutable(&self, place: &Expression) -> Result<(), CompilerError> {
+        let pointer = match &place.kind {
+            ExpressionKind::Primary(Primary::Identifier(identifier)) => {
+                let Some(variable) = self.get_variable(identifier) else {
+                    return Ok(());
+                };
+
+                if variable.mutable || self.is_never_assigned(&variable.location) {
+                    return Ok(());
+                }
+
+                return Err(CompilerError::new(
+                    place.pos.clone(),
+                    CompilerErrorKind::ImmutableAssignment(
+                        identifier.clone(),
+                        variable.pos.clone(),
+                    ),
+                ));
+            }
+            // Fields accessed through a pointer belong to the memory it points to
+            ExpressionKind::MemberAccess(access) if self.is_auto_deref(access)? => &access.left,
+            ExpressionKind::MemberAccess(access) => return self.check_mutable(&access.left),
+            // Elements of an array belong to the array itself
+            ExpressionKind::ArrayIndex(index)
+                if matches!(self.infer_type(&index.left)?, VariableType::Array(..)) =>
+            {
+                return self.check_mutable(&index.left)
+            }
+            ExpressionKind::Unary(Unary {
+                expr,
+                operator: Keyword::Asterix,
+                ..
+            }) => expr,
+            ExpressionKind::ArrayIndex(index) => &index.left,
+            _ => return Ok(()),
+        };
+
+        let typ = self.infer_type(pointer)?;
+
+        match &typ {
+            VariableType::Pointer(inner) | VariableType::Slice(inner)
+                if matches!(**inner, VariableType::Const(_)) =>
+            {
+                Err(CompilerError::new(
+                    place.pos.clone(),
+                    CompilerErrorKind::ConstAssignment(typ.clone()),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn handle_variable_declaration(
         &mut self,
         declaration: &VariableDeclaration,
     ) -> Result<Builder, CompilerError> {
-        if let Some(variable) = self.get_variable(&declaration.identifier) {
-            return Err(CompilerError::new(
-                declaration.identifier_pos.clone(),
-                CompilerErrorKind::RedeclaredVariable(
-                    declaration.identifier.clone(),
-                    variable.pos.clone(),
-                ),
-            ));
+        // Variables may be shadowed, but not parameters or items in the same scope
+        if let Some(variable) = self.get_local_variable(&declaration.identifier) {
+            let is_parameter = matches!(
+                &self.scope,
+                ProgramScope::FunctionScope(function_scope)
+                    if function_scope.is_parameter(&variable.location)
+            );
+            let is_item = matches!(
+                variable.typ,
+                VariableType::Function(_) | VariableType::Struct(_)
+            );
+
+            if is_parameter || is_item {
+                return Err(CompilerError::new(
+                    declaration.identifier_pos.clone(),
+                    CompilerErrorKind::RedeclaredVariable(
+                        declaration.identifier.clone(),
+                        variable.pos.clone(),
+                    ),
+                ));
+            }
         }
 
-        let infered = self.infer_type(&declaration.right)?;
+        let infered = declaration
+            .right
+            .as_ref()
+            .map(|right| self.infer_type(right))
+            .transpose()?;
         let typ = declaration
             .typ
             .as_ref()
-            .map(|typ| self.get_variable_type(typ));
+            .map(|typ| self.get_variable_type(typ))
+            .transpose()?;
+        let value_type = infered.clone();
 
-        if let Some(typ) = typ {
-            if typ != infered {
+        let typ = match (typ, infered) {
+            (Some(typ), Some(infered)) if !self.is_coercible(&infered, &typ) => {
                 return Err(CompilerError::new(
-                    declaration.right_pos.clone(),
+                    declaration.right.as_ref().unwrap().pos.clone(),
                     CompilerErrorKind::WrongType {
                         got: infered,
                         expected: typ,
+                        expected_alias: declaration
+                            .typ
+                            .as_ref()
+                            .and_then(|typ| self.get_type_alias(typ)),
                     },
                 ));
             }
-        }
+            (Some(typ), _) | (None, Some(typ)) => typ,
+            (None, None) => unreachable!("Parser requires either a type or a value"),
+        };
 
-        let size = Self::get_type_size(&infered);
+        // The value is handled before the variable is created,
+        // since it may refer to the variable being shadowed.
+        let is_global = matches!(self.scope, ProgramScope::RootScope(_));
+        let static_data = match &declaration.right {
+            Some(right) if is_global && self.is_static_expression(right) => {
+                Some(self.get_static_coerced_data(right, &typ)?)
+            }
+            _ => None,
+        };
+        let value = match (&declaration.right, &static_data) {
+            (Some(right), None) => Some(self.handle_coerced_expression(right, &typ)?),
+            _ => None,
+        };
+
+        let size = Self::get_type_size(&typ);
         let location = self.create_variable(
             declaration.identifier.clone(),
             Variable {
                 pos: declaration.identifier_pos.clone(),
-                typ: infered,
+                typ,
+                mutable: declaration.mutable,
             },
         );
 
-        let value = self.handle_expression(&declaration.right)?;
+        if let Some(value_type) = &value_type {
+            self.mark_assigned_type(&location, value_type);
+        }
+
+        if let VariableLocation::Global(label) = &location {
+            let data = match static_data {
+                Some(data) => GlobalData::Initialized(data),
+                // Whole 8 byte slots
+                None => GlobalData::Reserved(size.div_ceil(8)),
+            };
+
+            self.global_data.insert(label.clone(), data);
+        }
+
+        let (Some(right), Some(value)) = (&declaration.right, value) else {
+            let comment = match &declaration.right {
+                Some(_) => "Static variable declaration",
+                None => {
+                    self.mark_unassigned(&location);
+                    "Variable declaration without value"
+                }
+            };
+
+            return Ok(Builder::new().push(Procedure::new(
+                declaration.identifier_pos.clone(),
+                ProcedureKind::Comment(format!("{comment}: {}", declaration.identifier)),
+            )));
+        };
 
         let builder = Builder::new()
             .push(Procedure::new(
@@ -66,7 +194,7 @@ impl Program {
             .append(value);
 
         return Ok(builder.push(Procedure {
-            pos: declaration.identifier_pos.start..declaration.right_pos.end,
+            pos: declaration.identifier_pos.start..right.pos.end,
             comment: Some(format!("Assign: {}", declaration.identifier)),
             kind: ProcedureKind::Assign(Assign { location, size }),
         }));
@@ -79,6 +207,8 @@ impl Program {
         let infered_left = self.infer_type(&assignment.left)?;
         let infered_right = self.infer_type(&assignment.right)?;
 
+        self.check_mutable(&assignment.left)?;
+
         let builder = match &assignment.left.kind {
             ExpressionKind::Primary(Primary::Identifier(identifier)) => {
                 let Some(variable) = self.get_variable(identifier) else {
@@ -88,29 +218,36 @@ impl Program {
                     ));
                 };
 
-                if infered_left != infered_right {
+                // A `pointer?<T>` known to not be null may be set to null again
+                let typ = match &variable.typ {
+                    typ @ VariableType::Nullable(_) => typ.clone(),
+                    _ => infered_left,
+                };
+
+                if !Self::is_assignable(&infered_right, &typ) {
                     return Err(CompilerError::new(
                         assignment.right_pos.clone(),
                         CompilerErrorKind::WrongAssignmentType {
                             got: infered_right,
-                            typ: infered_left,
+                            typ,
                             declaration_pos: Some(variable.pos.clone()),
                         },
                     ));
                 }
 
                 let location = variable.location.clone();
+                let value = self.handle_expression(&assignment.right)?;
+                self.mark_assigned(&location);
+                self.mark_assigned_type(&location, &infered_right);
 
-                Builder::new()
-                    .append(self.handle_expression(&assignment.right)?)
-                    .push(Procedure {
-                        pos: assignment.left_pos.start..assignment.right_pos.end,
-                        comment: Some(format!("Reassign: {identifier}")),
-                        kind: ProcedureKind::Assign(Assign {
-                            location,
-                            size: Self::get_type_size(&infered_right),
-                        }),
-                    })
+                Builder::new().append(value).push(Procedure {
+                    pos: assignment.left_pos.start..assignment.right_pos.end,
+                    comment: Some(format!("Reassign: {identifier}")),
+                    kind: ProcedureKind::Assign(Assign {
+                        location,
+                        size: Self::get_type_size(&infered_right),
+                    }),
+                })
             }
             ExpressionKind::Unary(Unary {
                 expr,
@@ -121,7 +258,7 @@ impl Program {
                     todo!()
                 }
 
-                if infered_left != infered_right {
+                if !Self::is_assignable(&infered_right, &infered_left) {
                     return Err(CompilerError::new(
                         assignment.right_pos.clone(),
                         CompilerErrorKind::WrongAssignmentType {
@@ -137,21 +274,21 @@ impl Program {
                         assignment.left_pos.clone(),
                         ProcedureKind::Comment(format!("Assignment: {expr:?}")),
                     ))
-                    .append(self.handle_expression(expr)?)
+                    .append(self.handle_dereferenced_pointer(expr)?)
                     .append(self.handle_expression(&assignment.right)?)
                     .push(Procedure {
                         pos: assignment.left_pos.start..assignment.right_pos.end,
                         comment: Some("Reassign pointer value".to_string()),
                         kind: ProcedureKind::Assign(Assign {
                             location: VariableLocation::Address,
-                            size: Self::get_type_size(&VariableType::Value(Keyword::Int)), // todo: maybe wrong size?
+                            size: Self::get_type_size(&infered_left),
                         }),
                     })
             }
             ExpressionKind::MemberAccess(access) => {
-                let field_type = self.get_struct_field_type(&access.left, &access.member)?;
+                let field_type = self.get_struct_field_type(&assignment.left, access)?;
 
-                if infered_left != infered_right {
+                if !Self::is_assignable(&infered_right, &infered_left) {
                     return Err(CompilerError::new(
                         assignment.right_pos.clone(),
                         CompilerErrorKind::WrongAssignmentType {
@@ -174,6 +311,30 @@ impl Program {
                         }),
                     })
             }
+            ExpressionKind::ArrayIndex(index) => {
+                if !Self::is_assignable(&infered_right, &infered_left) {
+                    return Err(CompilerError::new(
+                        assignment.right_pos.clone(),
+                        CompilerErrorKind::WrongAssignmentType {
+                            got: infered_right,
+                            typ: infered_left,
+                            declaration_pos: None,
+                        },
+                    ));
+                }
+
+                Builder::new()
+                    .append(self.handle_array_index_without_deref(&assignment.left, index)?)
+                    .append(self.handle_expression(&assignment.right)?)
+                    .push(Procedure {
+                        pos: assignment.left_pos.start..assignment.right_pos.end,
+                        comment: Some("Reassign array element".to_string()),
+                        kind: ProcedureKind::Assign(Assign {
+                            location: VariableLocation::Address,
+                            size: Self::get_type_size(&infered_right),
+                        }),
+                    })
+            }
             _ => {
                 todo!("Unknown {:?}", assignment.left.kind)
             }
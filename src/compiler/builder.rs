@@ -2,7 +2,7 @@ use std::collections::LinkedList;
 
 use super::definition::Procedure;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Builder {
     pub procedures: LinkedList<Procedure>,
 }
@@ -0,0 +1,137 @@
+use crate::{
+    lexer::Keyword,
+    parser::definition::{Cast, Expression},
+};
+
+use super::{
+    builder::Builder,
+    definition::{Extend, OperandValue, Procedure, ProcedureKind, RegisterSize},
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::VariableType,
+};
+
+enum CastKind {
+    // The value can be used as it is
+    Reinterpret,
+
+    // The value has to be truncated and/or extended
+    Extend(Extend),
+}
+
+impl Program {
+    pub fn is_signed(primitive: &Keyword) -> bool {
+        !matches!(primitive, Keyword::Bool)
+    }
+
+    /// Returns `None` if the cast is not allowed.
+    fn get_cast_kind(from: &VariableType, to: &VariableType) -> Option<CastKind> {
+        if from == to {
+            return Some(CastKind::Reinterpret);
+        }
+
+        match (from, to) {
+            (
+                VariableType::Value(from @ (Keyword::Int | Keyword::Char | Keyword::Bool)),
+                VariableType::Value(to @ (Keyword::Int | Keyword::Char)),
+            ) => {
+                let from_size = Self::get_type_size(&VariableType::Value(*from));
+                let to_size = Self::get_type_size(&VariableType::Value(*to));
+
+                if from_size == to_size {
+                    return Some(CastKind::Reinterpret);
+                }
+
+                // Widening keeps the sign of the value,
+                // narrowing takes the sign of the new type.
+                let (size, signed) = if from_size < to_size {
+                    (from_size, Self::is_signed(from))
+                } else {
+                    (to_size, Self::is_signed(to))
+                };
+
+                Some(CastKind::Extend(Extend {
+                    size: RegisterSize::get_register(size),
+                    signed,
+                }))
+            }
+            (
+                VariableType::Pointer(_)
+                | VariableType::Nullable(_)
+                | VariableType::FunctionPointer(..),
+                VariableType::Pointer(_)
+                | VariableType::Nullable(_)
+                | VariableType::FunctionPointer(..)
+                | VariableType::Value(Keyword::Int),
+            )
+            | (
+                VariableType::Value(Keyword::Int),
+                VariableType::Pointer(_)
+                | VariableType::Nullable(_)
+                | VariableType::FunctionPointer(..),
+            ) => Some(CastKind::Reinterpret),
+            _ => None,
+        }
+    }
+
+    pub fn infer_cast_type(&self, cast: &Cast) -> Result<VariableType, CompilerError> {
+        let from = self.infer_type(&cast.expr)?;
+        let to = self.get_variable_type(&cast.typ)?;
+
+        if Self::get_cast_kind(&from, &to).is_none() && !self.is_coercible(&from, &to) {
+            return Err(CompilerError::new(
+                cast.expr.pos.start..cast.typ.pos.end,
+                CompilerErrorKind::IllegalCast { from, to },
+            ));
+        }
+
+        Ok(to)
+    }
+
+    pub fn handle_cast(
+        &mut self,
+        expression: &Expression,
+        cast: &Cast,
+    ) -> Result<Builder, CompilerError> {
+        let to = self.infer_cast_type(cast)?;
+        let from = self.infer_type(&cast.expr)?;
+        let builder = self.handle_coerced_expression(&cast.expr, &to)?;
+
+        Ok(match Self::get_cast_kind(&from, &to) {
+            Some(CastKind::Extend(extend)) => builder.push(Procedure::new(
+                expression.pos.clone(),
+                ProcedureKind::Extend(extend),
+            )),
+            _ => builder,
+        })
+    }
+
+    /// Casts the static value at compile time, the same way `handle_cast` does at runtime.
+    pub fn get_static_cast(&mut self, cast: &Cast) -> Result<Vec<OperandValue>, CompilerError> {
+        let to = self.infer_cast_type(cast)?;
+        let from = self.infer_type(&cast.expr)?;
+        let data = self.get_static_coerced_data(&cast.expr, &to)?;
+
+        let Some(CastKind::Extend(Extend { size, signed })) = Self::get_cast_kind(&from, &to)
+        else {
+            return Ok(data);
+        };
+
+        Ok(data
+            .into_iter()
+            .map(|value| match (value, &size, signed) {
+                (OperandValue::Int(i), RegisterSize::B8, true) => OperandValue::Int(i as i8 as i32),
+                (OperandValue::Int(i), RegisterSize::B8, false) => {
+                    OperandValue::Int(i as u8 as i32)
+                }
+                (OperandValue::Int(i), RegisterSize::B16, true) => {
+                    OperandValue::Int(i as i16 as i32)
+                }
+                (OperandValue::Int(i), RegisterSize::B16, false) => {
+                    OperandValue::Int(i as u16 as i32)
+                }
+                (value, ..) => value,
+            })
+            .collect())
+    }
+}
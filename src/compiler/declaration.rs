@@ -12,6 +12,9 @@ impl Program {
                 StatementKind::Expression(expression) => self.handle_expression(expression),
                 StatementKind::VariableAssignment(assignment) => self.handle_variable_assignment(assignment),
                 StatementKind::VariableDeclaration(declaration) => self.handle_variable_declaration(declaration),
+                StatementKind::TupleDeclaration(declaration) => {
+                    self.handle_tuple_declaration(declaration)
+                }
                 StatementKind::IfStatements(if_statements) => {
                     self.handle_if_statement(statement, if_statements)
                 }
@@ -19,13 +22,26 @@ impl Program {
                     self.handle_while_statement(statement, while_statement)
                 }
                 StatementKind::ReturnStatement(ret_statement) => self.handle_return_statement(statement, ret_statement),
+                StatementKind::DeferStatement(defer) => {
+                    self.handle_defer_statement(statement, defer)
+                }
             },
             DeclarationKind::FunctionDeclaration(fn_declaration) => {
                 self.handle_function_declaration(declaration, fn_declaration)
             }
+            DeclarationKind::ExternFunction(fn_declaration) => {
+                self.handle_extern_function(fn_declaration)
+            }
             DeclarationKind::StructDeclaration(struct_declaration) => {
                 self.handle_struct_declaration(declaration, struct_declaration)
             }
+            DeclarationKind::TypeAlias(alias) => self.handle_type_alias(declaration, alias),
+            DeclarationKind::TraitDeclaration(trait_declaration) => {
+                self.handle_trait_declaration(trait_declaration)
+            }
+            DeclarationKind::ImplDeclaration(impl_declaration) => {
+                self.handle_impl_declaration(impl_declaration)
+            }
         }
     }
 }
@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+use crate::parser::definition::{DeferStatement, Statement};
+
+use super::{
+    builder::Builder,
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::ProgramScope,
+};
+
+impl Program {
+    /// The deferred expression is compiled where it is declared,
+    /// so it refers to the variables visible there.
+    /// Its code is then emitted wherever the scope is exited, see `with_scope`
+    /// and `handle_return_statement`.
+    pub fn handle_defer_statement(
+        &mut self,
+        statement: &Statement,
+        defer: &DeferStatement,
+    ) -> Result<Builder, CompilerError> {
+        let ProgramScope::FunctionScope(_) = &self.scope else {
+            // There is no scope exit to run it at
+            return Err(CompilerError::new(
+                statement.pos.clone(),
+                CompilerErrorKind::DeferOutsideFunction,
+            ));
+        };
+
+        // Pointers may have been set to null by the time the deferred code runs
+        let non_null = self.get_non_null();
+        self.set_non_null(HashSet::new());
+        let value = self.handle_expression(&defer.value);
+        self.set_non_null(non_null);
+
+        let ProgramScope::FunctionScope(function_scope) = &mut self.scope else {
+            unreachable!();
+        };
+
+        function_scope.scope.deferred.push(value?);
+
+        Ok(Builder::new())
+    }
+}
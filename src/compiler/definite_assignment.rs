@@ -0,0 +1,154 @@
+use std::{collections::HashSet, ops::Range};
+
+use crate::parser::definition::{Expression, ExpressionKind};
+
+use super::{
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::{variable::VariableLocation, ProgramScope},
+};
+
+/// Definite assignment analysis.
+///
+/// Variables declared without a value are tracked in
+/// `FunctionScope::unassigned` until they are assigned.
+/// Variables which might have been assigned are tracked in `FunctionScope::assigned`,
+/// since variables which are not `mut` may only be assigned once.
+/// Global variables live in the bss section and are zeroed, so they are not tracked.
+impl Program {
+    pub fn get_unassigned(&self) -> HashSet<isize> {
+        match &self.scope {
+            ProgramScope::FunctionScope(scope) => scope.unassigned.clone(),
+            ProgramScope::RootScope(_) => HashSet::new(),
+        }
+    }
+
+    pub fn set_unassigned(&mut self, unassigned: HashSet<isize>) {
+        if let ProgramScope::FunctionScope(scope) = &mut self.scope {
+            scope.unassigned = unassigned;
+        }
+    }
+
+    pub fn get_assigned(&self) -> HashSet<isize> {
+        match &self.scope {
+            ProgramScope::FunctionScope(scope) => scope.assigned.clone(),
+            ProgramScope::RootScope(_) => HashSet::new(),
+        }
+    }
+
+    pub fn set_assigned(&mut self, assigned: HashSet<isize>) {
+        if let ProgramScope::FunctionScope(scope) = &mut self.scope {
+            scope.assigned = assigned;
+        }
+    }
+
+    /// Whether the variable was declared without a value,
+    /// and has not been assigned on any path to this point in the code.
+    pub fn is_never_assigned(&self, location: &VariableLocation) -> bool {
+        match (&self.scope, location) {
+            (ProgramScope::FunctionScope(scope), VariableLocation::Stack(loc)) => {
+                scope.unassigned.contains(loc) && !scope.assigned.contains(loc)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn mark_unassigned(&mut self, location: &VariableLocation) {
+        if let (ProgramScope::FunctionScope(scope), VariableLocation::Stack(loc)) =
+            (&mut self.scope, location)
+        {
+            scope.unassigned.insert(*loc);
+            scope.assigned.remove(loc);
+        }
+    }
+
+    pub fn mark_assigned(&mut self, location: &VariableLocation) {
+        if let (ProgramScope::FunctionScope(scope), VariableLocation::Stack(loc)) =
+            (&mut self.scope, location)
+        {
+            scope.unassigned.remove(loc);
+            scope.assigned.insert(*loc);
+        }
+    }
+
+    /// Code after this point can not be reached,
+    /// so every variable may be considered assigned, but not reassigned.
+    pub fn mark_unreachable(&mut self) {
+        self.set_unassigned(HashSet::new());
+        self.set_assigned(HashSet::new());
+    }
+
+    /// Errors if the variable might be read before it has been assigned.
+    pub fn check_assigned(
+        &self,
+        pos: Range<usize>,
+        identifier: &String,
+    ) -> Result<(), CompilerError> {
+        let (ProgramScope::FunctionScope(scope), Some(variable)) =
+            (&self.scope, self.get_variable(identifier))
+        else {
+            return Ok(());
+        };
+
+        match variable.location {
+            VariableLocation::Stack(loc) if scope.unassigned.contains(&loc) => {
+                Err(CompilerError::new(
+                    pos,
+                    CompilerErrorKind::UnassignedVariable(identifier.clone(), variable.pos.clone()),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the variable a chain of member accesses starts from, `a` in `a.b.c`.
+    pub fn check_member_access_assigned(
+        &self,
+        expression: &Expression,
+    ) -> Result<(), CompilerError> {
+        match &expression.kind {
+            ExpressionKind::MemberAccess(access) => self.check_member_access_assigned(&access.left),
+            _ => match expression.get_string() {
+                Some(identifier) => {
+                    self.check_assigned(expression.pos.clone(), &identifier.to_string())
+                }
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Runs each branch from the same starting point,
+    /// afterwards a variable is assigned only if it was assigned in every branch,
+    /// and might have been assigned if it was assigned in any branch.
+    /// If `exhaustive` is false, there is also a path which does not enter any branch.
+    pub fn with_branches<T, F>(
+        &mut self,
+        branches: &[T],
+        exhaustive: bool,
+        mut inner: F,
+    ) -> Result<(), CompilerError>
+    where
+        F: FnMut(&mut Self, &T) -> Result<(), CompilerError>,
+    {
+        let before = self.get_unassigned();
+        let before_assigned = self.get_assigned();
+        let (mut after, mut after_assigned) = if exhaustive {
+            (HashSet::new(), HashSet::new())
+        } else {
+            (before.clone(), before_assigned.clone())
+        };
+
+        for branch in branches {
+            self.set_unassigned(before.clone());
+            self.set_assigned(before_assigned.clone());
+            inner(self, branch)?;
+            after.extend(self.get_unassigned());
+            after_assigned.extend(self.get_assigned());
+        }
+
+        self.set_unassigned(after);
+        self.set_assigned(after_assigned);
+
+        Ok(())
+    }
+}
@@ -1,17 +1,21 @@
 use std::{
-    collections::BTreeMap,
+    collections::HashMap,
     fmt::{Display, Formatter},
     ops::Range,
+    rc::Rc,
 };
 
-use crate::parser::definition::FunctionDeclarationParameter;
+use crate::parser::{
+    definition::{FunctionDeclaration, TraitMethod},
+    types::Type,
+};
 
 use super::{
     builder::Builder,
     scope::variable::{VariableLocation, VariableType},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Procedure {
     pub pos: Range<usize>,
     pub kind: ProcedureKind,
@@ -28,18 +32,23 @@ impl Procedure {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ProcedureKind {
     Comment(String),
     Allocate(usize), // Allocate a certain amount of variables on the stack
     Deref,
+    DerefSlots(usize), // Loads all slots of a value larger than 8 bytes
     Push(OperandValue),
     PushAddress(OperandValue),
     Assign(Assign), // Stack position of the variable to assign
     Arithmetic(Arithmetic, RegisterSize),
+    Extend(Extend),
+    BoundsCheck, // Pops the length, and checks the index below it
+    NullCheck,   // Checks the pointer on top of the stack, without popping it
     SystemCall(SystemCall),
     ProcedureCall(ProcedureCall),
-    Return,
+    PointerCall(PointerCall),
+    Return(usize), // Slots taken by the returned value
     If(Vec<If>),
     While(While),
 }
@@ -57,7 +66,7 @@ impl Display for ProcedureKind {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum RegisterSize {
     B64,
@@ -107,7 +116,7 @@ impl RegisterSize {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Assign {
     // The start location of
     // the variable to assign to.
@@ -118,13 +127,23 @@ pub struct Assign {
     pub size: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct Extend {
+    // Truncate the value to this size
+    // before extending it back to 64 bits.
+    pub size: RegisterSize,
+
+    // Sign extend instead of zero extend.
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone)]
 pub struct While {
     pub condition: Box<Builder>,
     pub content: Box<Builder>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct If {
     pub condition: Option<Box<Builder>>,
     pub content: Box<Builder>,
@@ -139,6 +158,7 @@ pub enum Arithmetic {
     LessThan,
     GreaterThan,
     Equality,
+    NotEqual,
 }
 
 #[derive(Debug)]
@@ -148,39 +168,125 @@ pub struct FunctionDefinition {
     pub content: Box<Builder>,
 }
 
+#[derive(Debug, Clone)]
+pub struct FunctionParameter {
+    pub identifier: String,
+    pub mutable: bool,
+    pub typ: VariableType,
+    pub alias: Option<String>, // The type alias the parameter was declared with
+    pub pos: Range<usize>,
+}
+
 #[derive(Debug)]
 pub struct Function {
     pub identifier_pos: Range<usize>,
     pub return_type: Option<VariableType>,
-    pub parameters: Vec<FunctionDeclarationParameter>,
+    pub parameters: Vec<FunctionParameter>,
     pub body: Builder,
+    pub export: Option<String>, // Global symbol of functions declared with `export fn`
+}
+
+/// A C function declared with `extern fn`, called with the System V ABI.
+#[derive(Debug)]
+pub struct ExternFunction {
+    pub parameters: Vec<FunctionParameter>,
+    pub return_type: Option<VariableType>,
+    pub variadic: bool,
+}
+
+#[derive(Debug)]
+pub struct Trait {
+    pub identifier_pos: Range<usize>,
+    // The types of the methods refer to `Self`,
+    // so they are resolved for every implementation.
+    pub methods: Vec<TraitMethod>,
 }
 
+/// A function with type parameters, which is compiled
+/// once for every combination of types it is called with.
 #[derive(Debug)]
+pub struct GenericFunction {
+    pub pos: Range<usize>,
+    pub declaration: Rc<FunctionDeclaration>,
+    // The type arguments of every instance, and the id of its function
+    pub instances: Vec<(Vec<VariableType>, usize)>,
+}
+
+/// An instance of a generic function, which still has to be compiled.
+#[derive(Debug)]
+pub struct Instance {
+    pub function_id: usize,
+    pub pos: Range<usize>,
+    pub declaration: Rc<FunctionDeclaration>,
+    pub type_parameters: HashMap<String, VariableType>,
+}
+
+#[derive(Debug, Clone)]
 pub struct StructField {
     pub typ: VariableType,
     pub offset: usize,
     pub size: usize,
     pub pos: Range<usize>,
+    pub default: Option<Vec<OperandValue>>, // One value per 8 byte slot
+}
+
+/// Offsets of the fields, and the size and alignment of the whole struct.
+#[derive(Debug)]
+pub struct StructLayout {
+    pub offsets: Vec<usize>,
+    pub size: usize,
+    pub alignment: usize,
 }
 
 #[derive(Debug)]
 pub struct Struct {
-    pub fields: BTreeMap<String, StructField>,
-    pub size: usize, // Size of struct in bytes
+    pub fields: Vec<(String, StructField)>, // In declaration order
+    pub size: usize,                        // Size of struct in bytes, including padding
+    pub identifier_pos: Range<usize>,
+    pub methods: HashMap<String, usize>, // Function ids of the methods
+    pub traits: Vec<String>,             // Implemented traits
+}
+
+impl Struct {
+    pub fn get_field(&self, identifier: &str) -> Option<&StructField> {
+        self.fields
+            .iter()
+            .find(|(field_identifier, _)| field_identifier == identifier)
+            .map(|(_, field)| field)
+    }
 }
 
 #[derive(Debug)]
+pub struct TypeAlias {
+    pub typ: Type,
+    pub pos: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
 pub struct SystemCall {
     pub identifier: String,
-    pub nargs: usize,
+    pub args: Vec<usize>, // Slots taken by each argument, in the order they are pushed
+    pub return_slots: usize, // Slots taken by the returned value, 0 if nothing is returned
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ProcedureCall {
     pub function_id: usize,
-    pub nargs: usize,
-    pub returns: bool, // If the called function returns a value
+    pub args: Vec<usize>, // Slots taken by each argument, in the order they are pushed
+    pub return_slots: usize, // Slots taken by the returned value, 0 if nothing is returned
+}
+
+#[derive(Debug, Clone)]
+pub struct PointerCall {
+    pub args: Vec<usize>, // Slots taken by each argument, in the order they are pushed
+    pub return_slots: usize, // Slots taken by the returned value, 0 if nothing is returned
+}
+
+/// Where an argument is passed, following the System V calling convention.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentLocation {
+    Registers(usize), // Index of the register holding the first slot
+    Stack,            // Placed above the return address, after the previous stack arguments
 }
 
 #[derive(Debug)]
@@ -193,14 +299,22 @@ pub enum GlobalData {
     // Initialized .data literals
     String(String),
 
-    // Uninitialized .bss data
+    // Uninitialized .bss data, size in 8 byte slots
     Reserved(usize),
+
+    // Initialized .data values, one 8 byte slot each
+    Initialized(Vec<OperandValue>),
+
+    // Constant .rodata values, one 8 byte slot each, from the label upwards
+    ReadOnly(Vec<OperandValue>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum OperandValue {
-    StackLocation(isize), // usize relative to stack
-    DataLocation(String),
+    StackLocation(isize),        // usize relative to stack
+    DataLocation(String, usize), // label, and slot below the label
     DataPointerLocation(String),
+    FunctionLocation(usize), // function id
+    ArgumentRegister(usize), // index of the register an argument is passed in
     Int(i32),
 }
@@ -11,10 +11,63 @@ use super::scope::variable::VariableType;
 pub enum CompilerErrorKind {
     UndefinedVariable(String),
     UndefinedFunction(String),
+    UndefinedType(String),
+    CyclicTypeAlias(String),
+    UndefinedField {
+        identifier: String,
+        field: String,
+    },
+    ExpectedStruct(VariableType),
+    MissingField {
+        identifier: String,
+        field: String,
+        field_pos: Range<usize>,
+    },
+    UnknownField {
+        identifier: String,
+        field: String,
+        declaration_pos: Range<usize>,
+    },
+    DuplicateField {
+        field: String,
+        first_pos: Range<usize>,
+    },
+    NonStaticDefault(String),
+    TupleBindings {
+        typ: VariableType,
+        bindings: usize,
+    },
     RedeclaredVariable(String, Range<usize>),
+    UnassignedVariable(String, Range<usize>),
+    ImmutableAssignment(String, Range<usize>),
+    ConstAssignment(VariableType),
     DereferenceNonPointer(VariableType),
+    NullableDereference(VariableType),
+    EmptyArray,
+    UnboundedSlice(VariableType),
+    NonIntegerSliceBound(VariableType),
+    UndefinedSliceField(String),
+    IllegalCast {
+        from: VariableType,
+        to: VariableType,
+    },
     MissingMainFunction,
+    ExportedGenericFunction,
+    WrongReturnType {
+        got: VariableType,
+        expected: VariableType,
+        return_type_pos: Range<usize>,
+    },
+    MissingReturnValue {
+        expected: VariableType,
+        return_type_pos: Range<usize>,
+    },
+    UnexpectedReturnValue,
+    ExternType(VariableType),
+    ExternArgument(VariableType),
+    MissingReturn(VariableType),
     TooManyParametersInMainFunction,
+    WrongMainReturnType(VariableType),
     WrongBinaryExpressionTypes {
         got: VariableType,
         expected: VariableType,
@@ -25,9 +78,11 @@ pub enum CompilerErrorKind {
     WrongType {
         got: VariableType,
         expected: VariableType,
+        expected_alias: Option<String>,
     },
     WrongArgumentType {
         parameter: VariableType,
+        parameter_alias: Option<String>,
         argument: VariableType,
         parameter_pos: Range<usize>,
     },
@@ -36,7 +91,64 @@ pub enum CompilerErrorKind {
         typ: VariableType,
         declaration_pos: Option<Range<usize>>,
     },
-    
+    UndefinedTrait(String),
+    UndefinedMethod {
+        typ: VariableType,
+        method: String,
+    },
+    MethodAlreadyDefined {
+        typ: VariableType,
+        method: String,
+        first_pos: Range<usize>,
+    },
+    MissingTraitMethod {
+        trait_identifier: String,
+        method: String,
+        typ: VariableType,
+        method_pos: Range<usize>,
+    },
+    UnknownTraitMethod {
+        trait_identifier: String,
+        method: String,
+        trait_pos: Range<usize>,
+    },
+    WrongMethodSignature {
+        method: String,
+        got: VariableType,
+        expected: VariableType,
+        expected_pos: Range<usize>,
+    },
+    DynIncompatibleMethod {
+        trait_identifier: String,
+        method: String,
+        method_pos: Range<usize>,
+    },
+    UnsatisfiedBound {
+        typ: VariableType,
+        trait_identifier: String,
+        bound_pos: Range<usize>,
+    },
+    UninferredTypeParameter {
+        parameter: String,
+        parameter_pos: Range<usize>,
+    },
+    RedeclaredTrait(String, Range<usize>),
+    UnboundMethod {
+        parameter: String,
+        method: String,
+        parameter_pos: Range<usize>,
+    },
+    NestedDeclaration(&'static str),
+    GenericMethod(String),
+    MissingSelfParameter(String),
+    DeferOutsideFunction,
+    WrongArgumentCount {
+        expected: usize,
+        got: usize,
+    },
+    RedeclaredBuiltIn(String),
+    GenericExternFunction,
+
     #[allow(dead_code)]
     Todo {
         msg: String,
@@ -65,18 +177,31 @@ pub use compiler_todo;
 #[derive(Debug, PartialEq)]
 pub struct CompilerError {
     pub pos: Range<usize>,
-    pub kind: CompilerErrorKind,
+
+    // Boxed to keep the `Result`s returned throughout the compiler small
+    pub kind: Box<CompilerErrorKind>,
+}
+
+/// Displays the type, prefixed by the alias the user wrote if there is one.
+fn type_with_alias(typ: &VariableType, alias: &Option<String>) -> String {
+    match alias {
+        Some(alias) => format!("{alias} (aka {typ})"),
+        None => format!("{typ}"),
+    }
 }
 
 // Todo: Allow errors without positions,
 // todo: such as no-main function.
 impl CompilerError {
     pub fn new(pos: Range<usize>, kind: CompilerErrorKind) -> Self {
-        Self { pos, kind }
+        Self {
+            pos,
+            kind: Box::new(kind),
+        }
     }
 
     fn get_messages(&self) -> Vec<(String, Range<usize>)> {
-        match &self.kind {
+        match self.kind.as_ref() {
             CompilerErrorKind::Todo { file, line, msg } => vec![(
                 format!("Not yet implemented, {msg}. {file}:{line}"),
                 self.pos.clone(),
@@ -84,14 +209,83 @@ impl CompilerError {
             // todo: get_message should be a closure, accepting a document containing helper functions for getting lines.
             //  todo: perhaps a builder pattern to be able to show errors on multiple lines.
             CompilerErrorKind::MissingMainFunction => vec![("Missing main function".into(), 0..0)],
+            CompilerErrorKind::ExportedGenericFunction => vec![(
+                "Generic functions cannot be exported".into(),
+                self.pos.clone(),
+            )],
             CompilerErrorKind::TooManyParametersInMainFunction => vec![(
                 "Too many parameters for main function, expected maximum of 2".into(),
                 self.pos.clone(),
             )],
+            CompilerErrorKind::WrongReturnType {
+                got,
+                expected,
+                return_type_pos,
+            } => vec![
+                (
+                    format!("Wrong return type: {got}, expected: {expected}"),
+                    self.pos.clone(),
+                ),
+                ("Return type declared here".to_string(), return_type_pos.clone()),
+            ],
+            CompilerErrorKind::MissingReturnValue {
+                expected,
+                return_type_pos,
+            } => vec![
+                (
+                    format!("Missing return value of type {expected}"),
+                    self.pos.clone(),
+                ),
+                ("Return type declared here".to_string(), return_type_pos.clone()),
+            ],
+            CompilerErrorKind::UnexpectedReturnValue => vec![(
+                "Cannot return a value from a function without a return type".into(),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::ExternType(typ) => vec![(
+                format!("Values of type {typ} cannot be passed to or returned from external functions, they do not fit in a register"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::ExternArgument(typ) => vec![(
+                format!("Values of type {typ} cannot be passed as variadic arguments to external functions, they do not fit in a register"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::MissingReturn(typ) => vec![(
+                format!("Not all paths return a value of type {typ}"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::WrongMainReturnType(typ) => vec![(
+                format!("Main function has to return int or nothing, got {typ}"),
+                self.pos.clone(),
+            )],
             CompilerErrorKind::DereferenceNonPointer(typ) => vec![(
                 format!("Cannot dereference non-pointer value of type {typ}"),
                 self.pos.clone(),
             )],
+            CompilerErrorKind::NullableDereference(typ) => vec![(
+                format!("Cannot dereference value of type {typ}, it has to be compared to null first"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::EmptyArray => vec![(
+                "Cannot infer the type of an empty array".into(),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::UnboundedSlice(typ) => vec![(
+                format!("Cannot slice a value of type {typ} without an end index"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::NonIntegerSliceBound(typ) => vec![(
+                format!("Slices are bounded by int indices, got {typ}"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::UndefinedSliceField(field) => vec![(
+                format!("Slices have no field {field}, only len"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::IllegalCast { from, to } => vec![(
+                format!("Cannot cast value of type {from} to {to}"),
+                self.pos.clone(),
+            )],
             CompilerErrorKind::RedeclaredVariable(identifier, pos) => vec![
                 (
                     format!("Redeclared variable: {identifier}"),
@@ -99,6 +293,27 @@ impl CompilerError {
                 ),
                 ("Already declared here".to_string(), pos.clone()),
             ],
+            CompilerErrorKind::UnassignedVariable(identifier, pos) => vec![
+                (
+                    format!("Variable {identifier} is used before being assigned"),
+                    self.pos.clone(),
+                ),
+                ("Declared here without a value".to_string(), pos.clone()),
+            ],
+            CompilerErrorKind::ImmutableAssignment(identifier, pos) => vec![
+                (
+                    format!("Cannot assign to immutable variable {identifier}"),
+                    self.pos.clone(),
+                ),
+                (
+                    format!("Declared here, consider making it mutable: `mut {identifier}`"),
+                    pos.clone(),
+                ),
+            ],
+            CompilerErrorKind::ConstAssignment(typ) => vec![(
+                format!("Cannot assign through pointer of type {typ}"),
+                self.pos.clone(),
+            )],
             CompilerErrorKind::UndefinedVariable(identifier) => {
                 vec![(
                     format!("Undefined variable: {identifier}"),
@@ -111,6 +326,204 @@ impl CompilerError {
                     self.pos.clone(),
                 )]
             }
+            CompilerErrorKind::UndefinedType(identifier) => {
+                vec![(format!("Undefined type: {identifier}"), self.pos.clone())]
+            }
+            CompilerErrorKind::CyclicTypeAlias(identifier) => {
+                vec![(
+                    format!("Type alias {identifier} is defined in terms of itself"),
+                    self.pos.clone(),
+                )]
+            }
+            CompilerErrorKind::UndefinedField { identifier, field } => {
+                vec![(
+                    format!("Struct {identifier} has no field {field}"),
+                    self.pos.clone(),
+                )]
+            }
+            CompilerErrorKind::ExpectedStruct(typ) => {
+                vec![(
+                    format!("Expected a struct type, got {typ}"),
+                    self.pos.clone(),
+                )]
+            }
+            CompilerErrorKind::MissingField {
+                identifier,
+                field,
+                field_pos,
+            } => vec![
+                (
+                    format!("Missing field {field} in construction of {identifier}"),
+                    self.pos.clone(),
+                ),
+                (
+                    "Declared here without a default value".to_string(),
+                    field_pos.clone(),
+                ),
+            ],
+            CompilerErrorKind::UnknownField {
+                identifier,
+                field,
+                declaration_pos,
+            } => vec![
+                (
+                    format!("Struct {identifier} has no field {field}"),
+                    self.pos.clone(),
+                ),
+                (format!("{identifier} is declared here"), declaration_pos.clone()),
+            ],
+            CompilerErrorKind::DuplicateField { field, first_pos } => vec![
+                (format!("Field {field} is given more than once"), self.pos.clone()),
+                ("First given here".to_string(), first_pos.clone()),
+            ],
+            CompilerErrorKind::TupleBindings { typ, bindings } => vec![(
+                format!("Cannot destructure a value of type {typ} into {bindings} variables"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::NonStaticDefault(field) => vec![(
+                format!("Default value of field {field} has to be known at compile time"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::UndefinedTrait(identifier) => {
+                vec![(format!("Undefined trait: {identifier}"), self.pos.clone())]
+            }
+            CompilerErrorKind::UndefinedMethod { typ, method } => vec![(
+                format!("Type {typ} has no method {method}"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::MethodAlreadyDefined {
+                typ,
+                method,
+                first_pos,
+            } => vec![
+                (
+                    format!("Method {method} is already defined for {typ}"),
+                    self.pos.clone(),
+                ),
+                ("First defined here".to_string(), first_pos.clone()),
+            ],
+            CompilerErrorKind::MissingTraitMethod {
+                trait_identifier,
+                method,
+                typ,
+                method_pos,
+            } => vec![
+                (
+                    format!("Implementation of {trait_identifier} for {typ} is missing method {method}"),
+                    self.pos.clone(),
+                ),
+                (format!("{method} is declared here"), method_pos.clone()),
+            ],
+            CompilerErrorKind::UnknownTraitMethod {
+                trait_identifier,
+                method,
+                trait_pos,
+            } => vec![
+                (
+                    format!("Method {method} is not a member of trait {trait_identifier}"),
+                    self.pos.clone(),
+                ),
+                (format!("{trait_identifier} is declared here"), trait_pos.clone()),
+            ],
+            CompilerErrorKind::WrongMethodSignature {
+                method,
+                got,
+                expected,
+                expected_pos,
+            } => vec![
+                (
+                    format!("Method {method} has type {got}, expected: {expected}"),
+                    self.pos.clone(),
+                ),
+                ("Declared in the trait here".to_string(), expected_pos.clone()),
+            ],
+            CompilerErrorKind::DynIncompatibleMethod {
+                trait_identifier,
+                method,
+                method_pos,
+            } => vec![
+                (
+                    format!("Method {method} cannot be called on &dyn {trait_identifier}"),
+                    self.pos.clone(),
+                ),
+                (
+                    "Only methods taking `self: &Self`, and not using Self otherwise, can be called through a vtable".to_string(),
+                    method_pos.clone(),
+                ),
+            ],
+            CompilerErrorKind::UnsatisfiedBound {
+                typ,
+                trait_identifier,
+                bound_pos,
+            } => vec![
+                (
+                    format!("Type {typ} does not implement trait {trait_identifier}"),
+                    self.pos.clone(),
+                ),
+                ("Required by this bound".to_string(), bound_pos.clone()),
+            ],
+            CompilerErrorKind::UninferredTypeParameter {
+                parameter,
+                parameter_pos,
+            } => vec![
+                (
+                    format!("Cannot infer the type of type parameter {parameter}"),
+                    self.pos.clone(),
+                ),
+                ("Declared here".to_string(), parameter_pos.clone()),
+            ],
+            CompilerErrorKind::UnboundMethod {
+                parameter,
+                method,
+                parameter_pos,
+            } => vec![
+                (
+                    format!("Type parameter {parameter} has no method {method}"),
+                    self.pos.clone(),
+                ),
+                (
+                    format!("Declared here without a bound providing {method}"),
+                    parameter_pos.clone(),
+                ),
+            ],
+            CompilerErrorKind::RedeclaredTrait(identifier, pos) => vec![
+                (
+                    format!("Trait {identifier} is already declared"),
+                    self.pos.clone(),
+                ),
+                ("Already declared here".to_string(), pos.clone()),
+            ],
+            CompilerErrorKind::NestedDeclaration(declarations) => vec![(
+                format!("{declarations} can only be declared outside of functions"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::GenericMethod(method) => vec![(
+                format!("Method {method} cannot be generic, only functions can"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::WrongArgumentCount { expected, got } => vec![(
+                format!(
+                    "Expected {expected} argument{}, got {got}",
+                    if *expected == 1 { "" } else { "s" }
+                ),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::RedeclaredBuiltIn(identifier) => vec![(
+                format!("{identifier} is a built-in function and cannot be redeclared"),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::GenericExternFunction => vec![(
+                "External functions cannot be generic".into(),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::DeferOutsideFunction => vec![(
+                "Code can only be deferred inside of functions".to_string(),
+                self.pos.clone(),
+            )],
+            CompilerErrorKind::MissingSelfParameter(method) => vec![(
+                format!("Method {method} has to take the value it is called on as its first parameter `self`"),
+                self.pos.clone(),
+            )],
             CompilerErrorKind::WrongBinaryExpressionTypes {
                 got,
                 expected,
@@ -147,6 +560,7 @@ impl CompilerError {
             CompilerErrorKind::WrongArgumentType {
                 argument,
                 parameter,
+                parameter_alias,
                 parameter_pos,
             } => {
                 vec![
@@ -155,14 +569,24 @@ impl CompilerError {
                         self.pos.clone(),
                     ),
                     (
-                        format!("Function takes parameter of type: {parameter}"),
+                        format!(
+                            "Function takes parameter of type: {}",
+                            type_with_alias(parameter, parameter_alias)
+                        ),
                         parameter_pos.clone(),
                     ),
                 ]
             }
-            CompilerErrorKind::WrongType { got, expected } => {
+            CompilerErrorKind::WrongType {
+                got,
+                expected,
+                expected_alias,
+            } => {
                 vec![(
-                    format!("Wrong type: {got}, expected: {expected}"),
+                    format!(
+                        "Wrong type: {got}, expected: {}",
+                        type_with_alias(expected, expected_alias)
+                    ),
                     self.pos.clone(),
                 )]
             }
@@ -10,6 +10,38 @@ use crate::{
 };
 
 impl Program {
+    /// Procedures pushing the value of a stored variable to the stack.
+    /// If `load_address`, pushes its address instead.
+    pub fn get_push_procedures(
+        typ: &VariableType,
+        location: &VariableLocation,
+        load_address: bool,
+    ) -> Vec<ProcedureKind> {
+        let slots = Self::get_type_slots(typ);
+        let slot_operand = |slot: usize| match location {
+            VariableLocation::Stack(loc) => OperandValue::StackLocation(loc + slot as isize),
+            VariableLocation::Global(label) => OperandValue::DataLocation(label.clone(), slot),
+            VariableLocation::Address => {
+                todo!()
+            }
+        };
+
+        match (typ, load_address) {
+            // Functions are referenced by their label
+            (VariableType::Function(function_id), _) => {
+                vec![ProcedureKind::Push(OperandValue::FunctionLocation(
+                    *function_id,
+                ))]
+            }
+            // Values start at their lowest slot, which is the last one
+            (_, true) => vec![ProcedureKind::PushAddress(slot_operand(slots - 1))],
+            (_, false) => (0..slots)
+                .rev()
+                .map(|slot| ProcedureKind::Push(slot_operand(slot)))
+                .collect(),
+        }
+    }
+
     /// Pushes the value of the identifier to the stack.
     /// If `load_address`, uses `LEA` instead of `MOV`
     pub fn handle_identifier(
@@ -18,30 +50,24 @@ impl Program {
         identifier: &String,
         load_address: bool,
     ) -> Result<Builder, CompilerError> {
-        if let Some(variable) = self.get_variable(identifier) {
-            let operand_value = match &variable.location {
-                VariableLocation::Stack(loc) => OperandValue::StackLocation(*loc),
-                VariableLocation::Global(label) => {
-                    let is_pointer = matches!(&variable.typ, VariableType::Pointer(_));
-
-                    if is_pointer {
-                        OperandValue::DataPointerLocation(label.clone())
-                    } else {
-                        OperandValue::DataLocation(label.clone())
-                    }
-                }
-                VariableLocation::Address => {
-                    todo!()
-                }
-            };
-
-            let operation = if load_address {
-                ProcedureKind::PushAddress(operand_value)
-            } else {
-                ProcedureKind::Push(operand_value)
-            };
+        if load_address {
+            // The variable might be assigned through the pointer
+            if let Some(location) = self.get_variable(identifier).map(|v| v.location.clone()) {
+                self.mark_assigned(&location);
+                self.mark_nullable(&location);
+            }
+        } else {
+            self.check_assigned(expression.pos.clone(), identifier)?;
+        }
 
-            Ok(Builder::new().push(Procedure::new(expression.pos.clone(), operation)))
+        if let Some(variable) = self.get_variable(identifier) {
+            Ok(
+                Self::get_push_procedures(&variable.typ, &variable.location, load_address)
+                    .into_iter()
+                    .fold(Builder::new(), |builder, operation| {
+                        builder.push(Procedure::new(expression.pos.clone(), operation))
+                    }),
+            )
         } else {
             Err(CompilerError::new(
                 expression.pos.clone(),
@@ -10,17 +10,25 @@ use crate::{
 };
 
 impl Program {
-    fn handle_string_literal(
-        &mut self,
-        expression: &Expression,
-        string: &str,
-    ) -> Result<Builder, CompilerError> {
+    /// Adds the string to the data section.
+    /// Returns the label of the string.
+    pub fn add_string_literal(&mut self, string: &str) -> String {
         let label = format!("_literal_{}", self.literal_index);
         self.literal_index += 1;
 
         self.global_data
             .insert(label.clone(), GlobalData::String(string.to_string()));
 
+        label
+    }
+
+    fn handle_string_literal(
+        &mut self,
+        expression: &Expression,
+        string: &str,
+    ) -> Result<Builder, CompilerError> {
+        let label = self.add_string_literal(string);
+
         Ok(Builder::new().push(Procedure::new(
             expression.pos.clone(),
             ProcedureKind::Push(OperandValue::DataPointerLocation(label)),
@@ -58,6 +66,7 @@ impl Program {
             Literal::String(s) => self.handle_string_literal(expression, s),
             Literal::Int(i) => self.handle_int_literal(expression, *i),
             Literal::Bool(b) => self.handle_bool_literal(expression, *b),
+            Literal::Null => self.handle_int_literal(expression, 0),
         }
     }
 }
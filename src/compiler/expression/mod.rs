@@ -27,6 +27,7 @@ impl Program {
             Keyword::LessThan => Arithmetic::LessThan,
             Keyword::GreaterThan => Arithmetic::GreaterThan,
             Keyword::Equality => Arithmetic::Equality,
+            Keyword::NotEqual => Arithmetic::NotEqual,
             _ => todo!("Not supported"),
         }
     }
@@ -60,6 +61,7 @@ impl Program {
                 Arithmetic::Add
                 | Arithmetic::Subtract
                 | Arithmetic::Equality
+                | Arithmetic::NotEqual
                 | Arithmetic::GreaterThan
                 | Arithmetic::LessThan,
             ) => {
@@ -75,6 +77,18 @@ impl Program {
                         ProcedureKind::Arithmetic(operation, RegisterSize::B64),
                     )))
             }
+            // Pointers which may be null, compared to each other or to null
+            (
+                VariableType::Pointer(_) | VariableType::Nullable(_) | VariableType::Null,
+                VariableType::Pointer(_) | VariableType::Nullable(_) | VariableType::Null,
+                Arithmetic::Equality | Arithmetic::NotEqual,
+            ) => Ok(Builder::new()
+                .append(self.handle_expression(&binary.right)?)
+                .append(self.handle_expression(&binary.left)?)
+                .push(Procedure::new(
+                    expression.pos.clone(),
+                    ProcedureKind::Arithmetic(operation, RegisterSize::B64),
+                ))),
             (
                 VariableType::Value(Keyword::Int | Keyword::Char),
                 VariableType::Value(Keyword::Int | Keyword::Char),
@@ -128,10 +142,15 @@ impl Program {
                 self.handle_struct_construction(expression, sconst)
             }
             ExpressionKind::ArrayIndex(index) => self.handle_array_index(expression, index),
+            ExpressionKind::ArraySlice(slice) => self.handle_array_slice(expression, slice),
             ExpressionKind::MemberAccess(access) => self.handle_member_access(expression, access),
             ExpressionKind::Primary(primary) => self.handle_primary(expression, primary),
             ExpressionKind::Unary(unary) => self.handle_unary(expression, unary),
             ExpressionKind::Binary(binary) => self.handle_binary(expression, binary),
+            ExpressionKind::Cast(cast) => self.handle_cast(expression, cast),
+            ExpressionKind::TypeInfo(info) => self.handle_type_info(expression, info),
+            ExpressionKind::ArrayLiteral(array) => self.handle_array_literal(expression, array),
+            ExpressionKind::Tuple(elements) => self.handle_tuple(expression, elements),
         }
     }
 }
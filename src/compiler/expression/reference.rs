@@ -1,38 +1,99 @@
+use std::ops::Range;
+
 use crate::{
     compiler::{
         builder::Builder,
-        definition::{Procedure, ProcedureKind},
+        definition::{Assign, Extend, Procedure, ProcedureKind, RegisterSize},
         error::CompilerError,
         program::Program,
+        scope::variable::VariableType,
     },
     lexer::Keyword,
-    parser::definition::{Expression, ExpressionKind, Primary},
+    parser::definition::{Expression, ExpressionKind, Primary, Unary},
 };
 
 impl Program {
     pub fn handle_ref(&mut self, expression: &Expression) -> Result<Builder, CompilerError> {
         match &expression.kind {
-            ExpressionKind::Primary(primary) => match &primary {
-                Primary::Identifier(identifier) => Ok(
-                    Builder::new().append(self.handle_identifier(expression, identifier, true)?)
-                ),
-                _ => todo!("Not supported"),
-            },
+            ExpressionKind::Primary(Primary::Identifier(identifier)) => {
+                Ok(Builder::new().append(self.handle_identifier(expression, identifier, true)?))
+            }
             ExpressionKind::MemberAccess(access) => {
                 self.handle_member_access_without_deref(expression, access)
             }
             ExpressionKind::ArrayIndex(index) => {
                 self.handle_array_index_without_deref(expression, index)
             }
-            ExpressionKind::Unary(unary) => match unary.operator {
-                Keyword::Asterix => Ok(self
-                    .handle_ref(&unary.expr)?
-                    .push(Procedure::new(unary.expr.pos.clone(), ProcedureKind::Deref))),
-                _ => todo!("Unary operator not supported {:?}", unary.operator),
+            ExpressionKind::Unary(Unary {
+                expr,
+                operator: Keyword::Asterix,
+                ..
+            }) => {
+                let pointer = self
+                    .handle_ref(expr)?
+                    .push(Procedure::new(expr.pos.clone(), ProcedureKind::Deref));
+
+                Ok(self.with_null_check(pointer, &expr.pos))
+            }
+            _ => self.handle_temporary_ref(expression),
+        }
+    }
+
+    /// Values which are not stored anywhere, like returned values,
+    /// are stored in a temporary variable to get their address.
+    fn handle_temporary_ref(&mut self, expression: &Expression) -> Result<Builder, CompilerError> {
+        let typ = self.infer_type(expression)?;
+        let value = self.handle_expression(expression)?;
+        let location = self.create_temporary(".temporary", &typ, expression.pos.clone());
+
+        Ok(Self::get_push_procedures(&typ, &location, true)
+            .into_iter()
+            .fold(
+                value.push(Procedure::new(
+                    expression.pos.clone(),
+                    ProcedureKind::Assign(Assign {
+                        location: location.clone(),
+                        size: Self::get_type_size(&typ),
+                    }),
+                )),
+                |builder, kind| builder.push(Procedure::new(expression.pos.clone(), kind)),
+            ))
+    }
+
+    /// Loads a value of the type from the address on top of the stack.
+    /// The bytes after values smaller than a slot belong to other values,
+    /// so they are cleared.
+    pub fn get_load(typ: &VariableType, pos: &Range<usize>) -> Builder {
+        let builder = Builder::new().push(Procedure::new(
+            pos.clone(),
+            match Self::get_type_slots(typ) {
+                1 => ProcedureKind::Deref,
+                slots => ProcedureKind::DerefSlots(slots),
             },
-            _ => {
-                todo!("Not supported {:#?}", expression.kind);
+        ));
+
+        match Self::get_register_extend(typ) {
+            Some(extend) => {
+                builder.push(Procedure::new(pos.clone(), ProcedureKind::Extend(extend)))
             }
+            None => builder,
+        }
+    }
+
+    /// Values smaller than a register are extended to all of it,
+    /// when they are loaded from memory, or passed to or returned from C functions.
+    pub fn get_register_extend(typ: &VariableType) -> Option<Extend> {
+        match typ {
+            VariableType::Const(typ) => Self::get_register_extend(typ),
+            VariableType::Value(primitive) if Self::get_type_size(typ) < 8 => Some(Extend {
+                size: RegisterSize::get_register(Self::get_type_size(typ)),
+                signed: Self::is_signed(primitive),
+            }),
+            _ if matches!(Self::get_type_size(typ), 1 | 2 | 4) => Some(Extend {
+                size: RegisterSize::get_register(Self::get_type_size(typ)),
+                signed: false,
+            }),
+            _ => None,
         }
     }
 
@@ -41,11 +102,10 @@ impl Program {
         complete_expression: &Expression,
         expression: &Expression,
     ) -> Result<Builder, CompilerError> {
+        let typ = self.infer_type(complete_expression)?;
+
         Ok(Builder::new()
-            .append(self.handle_expression(expression)?)
-            .push(Procedure::new(
-                complete_expression.pos.clone(),
-                ProcedureKind::Deref,
-            )))
+            .append(self.handle_dereferenced_pointer(expression)?)
+            .append(Self::get_load(&typ, &complete_expression.pos)))
     }
 }
@@ -0,0 +1,74 @@
+use std::ops::Range;
+
+use crate::parser::definition::FunctionDeclaration;
+
+use super::{
+    builder::Builder,
+    definition::ExternFunction,
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::{variable::VariableType, ProgramScope},
+};
+
+impl Program {
+    /// `extern fn puts(s: &char) -> int;` declares a function linked from C.
+    /// Only values which fit in a single register can be passed and returned,
+    /// `int` is 64 bits wide like a C `long`.
+    pub fn handle_extern_function(
+        &mut self,
+        fdec: &FunctionDeclaration,
+    ) -> Result<Builder, CompilerError> {
+        let ProgramScope::RootScope(_) = &self.scope else {
+            return Err(CompilerError::new(
+                fdec.identifier_pos.clone(),
+                CompilerErrorKind::NestedDeclaration("External functions"),
+            ));
+        };
+
+        // C functions are compiled for a single signature
+        if !fdec.generics.is_empty() {
+            return Err(CompilerError::new(
+                fdec.identifier_pos.clone(),
+                CompilerErrorKind::GenericExternFunction,
+            ));
+        }
+
+        let (parameters, return_type) = self.get_function_signature(fdec, &self.type_parameters)?;
+
+        for parameter in &parameters {
+            Self::check_extern_type(
+                &parameter.typ,
+                &parameter.pos,
+                CompilerErrorKind::ExternType,
+            )?;
+        }
+
+        if let (Some(typ), Some(declared)) = (&return_type, &fdec.return_type) {
+            Self::check_extern_type(typ, &declared.pos, CompilerErrorKind::ExternType)?;
+        }
+
+        self.extern_functions.insert(
+            fdec.identifier.clone(),
+            ExternFunction {
+                parameters,
+                return_type,
+                variadic: fdec.variadic,
+            },
+        );
+
+        Ok(Builder::new())
+    }
+
+    /// C functions take and return values in single registers.
+    pub fn check_extern_type(
+        typ: &VariableType,
+        pos: &Range<usize>,
+        kind: fn(VariableType) -> CompilerErrorKind,
+    ) -> Result<(), CompilerError> {
+        if Self::get_type_slots(typ) > 1 {
+            return Err(CompilerError::new(pos.clone(), kind(typ.clone())));
+        }
+
+        Ok(())
+    }
+}
@@ -1,39 +1,498 @@
-use crate::parser::definition::{Expression, FunctionCall};
+use std::ops::Range;
+
+use crate::{
+    lexer::Keyword,
+    parser::definition::{Expression, ExpressionKind, FunctionCall, MemberAccess},
+};
 
 use super::{
     builder::Builder,
-    definition::{Procedure, ProcedureCall, ProcedureKind, SystemCall},
+    definition::{
+        ArgumentLocation, PointerCall, Procedure, ProcedureCall, ProcedureKind, SystemCall,
+    },
     error::{CompilerError, CompilerErrorKind},
     program::Program,
     scope::variable::VariableType,
 };
 
-static BUILT_IN: &[&str] = &["printf"];
+static BUILT_IN: &[&str] = &["printf", "exit"];
+
+/// Parameter type, the alias it was declared with, and its position.
+pub type Parameter = (VariableType, Option<String>, Range<usize>);
+
+/// What is called after the arguments have been pushed.
+enum CallTarget {
+    Function(usize),
+    // Pushes the function pointer
+    Pointer(Builder),
+    // Function declared with `extern fn`
+    Extern(String),
+}
 
 impl Program {
-    pub fn handle_function_call(
+    /// Calls to built-in functions are compiled before functions are looked up,
+    /// so functions with their identifiers could never be called.
+    pub fn check_not_built_in(identifier: &str, pos: &Range<usize>) -> Result<(), CompilerError> {
+        if BUILT_IN.contains(&identifier) {
+            return Err(CompilerError::new(
+                pos.clone(),
+                CompilerErrorKind::RedeclaredBuiltIn(identifier.to_string()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn check_arguments(
+        &self,
+        args: &[Expression],
+        parameters: &[Parameter],
+        args_pos: &Range<usize>,
+    ) -> Result<(), CompilerError> {
+        if parameters.len() != args.len() {
+            return Err(CompilerError::new(
+                args_pos.clone(),
+                CompilerErrorKind::WrongArgumentCount {
+                    expected: parameters.len(),
+                    got: args.len(),
+                },
+            ));
+        }
+
+        for ((par_type, par_alias, par_pos), arg) in parameters.iter().zip(args) {
+            let arg_type = Self::decay_type(self.infer_type(arg)?);
+
+            if !self.is_coercible(&arg_type, par_type) {
+                return Err(CompilerError::new(
+                    arg.pos.clone(),
+                    CompilerErrorKind::WrongArgumentType {
+                        parameter: par_type.clone(),
+                        parameter_alias: par_alias.clone(),
+                        argument: arg_type,
+                        parameter_pos: par_pos.clone(),
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Arrays are passed to functions as a pointer to their first element.
+    pub fn decay_type(typ: VariableType) -> VariableType {
+        match typ {
+            VariableType::Array(value_type, _) => VariableType::Pointer(value_type),
+            typ => typ,
+        }
+    }
+
+    /// Amount of registers arguments are passed in, see `get_argument_locations`.
+    pub const ARGUMENT_REGISTERS: usize = 6;
+
+    /// Arguments of up to two slots are passed in consecutive registers,
+    /// as long as there are enough of them left.
+    /// The remaining arguments are placed on the stack, the first one at the lowest address.
+    pub fn get_argument_locations(args: &[usize]) -> Vec<ArgumentLocation> {
+        let mut registers = 0;
+
+        args.iter()
+            .map(|&slots| {
+                if slots <= 2 && registers + slots <= Self::ARGUMENT_REGISTERS {
+                    registers += slots;
+                    ArgumentLocation::Registers(registers - slots)
+                } else {
+                    ArgumentLocation::Stack
+                }
+            })
+            .collect()
+    }
+
+    /// Slots taken by the arguments passed for the parameters.
+    fn get_argument_slots(parameters: &[Parameter]) -> Vec<usize> {
+        parameters
+            .iter()
+            .map(|(typ, ..)| Self::get_type_slots(typ))
+            .collect()
+    }
+
+    /// Values larger than this amount of slots are returned through a hidden pointer
+    /// to memory reserved by the caller, smaller values are returned in registers.
+    pub const MAX_RETURN_SLOTS: usize = 2;
+
+    /// The type returned by calling the function with the given identifier.
+    pub fn get_return_type(
+        &self,
+        fcall: &FunctionCall,
+    ) -> Result<Option<VariableType>, CompilerError> {
+        if let ExpressionKind::MemberAccess(access) = &fcall.left.kind {
+            if let VariableType::Dyn(trait_identifier) =
+                self.infer_type(&access.left)?.without_const()
+            {
+                let method = self.get_dyn_method(&fcall.left, access, &trait_identifier)?;
+                return Ok(method.return_type);
+            }
+
+            let function_id = self.get_method(&fcall.left, access)?;
+            return Ok(self.functions[function_id].return_type.clone());
+        }
+
+        let identifier = fcall.left.get_string().unwrap().to_string();
+
+        if let Some(function) = self.extern_functions.get(&identifier) {
+            return Ok(function.return_type.clone());
+        }
+
+        if let Some(generic) = self.generic_functions.get(&identifier) {
+            let type_parameters = self.get_type_arguments(generic, fcall)?;
+            let (_, return_type) =
+                self.get_function_signature(&generic.declaration, &type_parameters)?;
+
+            return Ok(return_type);
+        }
+
+        let Some(function) = self.get_variable(&identifier) else {
+            return Err(CompilerError::new(
+                fcall.left.pos.clone(),
+                CompilerErrorKind::UndefinedFunction(identifier),
+            ));
+        };
+
+        Ok(match &function.typ {
+            VariableType::Function(function_id) => self
+                .functions
+                .get(*function_id)
+                .unwrap()
+                .return_type
+                .clone(),
+            VariableType::FunctionPointer(_, return_type) => {
+                return_type.clone().map(|return_type| *return_type)
+            }
+            _ => todo!("Variable is not a function"),
+        })
+    }
+
+    /// Calls the target after the arguments have been pushed, and pushes the returned value.
+    fn handle_call(
         &mut self,
         expression: &Expression,
-        fcall: &FunctionCall,
+        arguments: Builder,
+        mut args: Vec<usize>,
+        return_type: Option<VariableType>,
+        target: CallTarget,
     ) -> Result<Builder, CompilerError> {
         let mut builder = Builder::new();
+        let return_slots = return_type.as_ref().map_or(0, Self::get_type_slots);
+
+        // C functions only set the bytes of the register which belong to the returned type
+        let extend = return_type.as_ref().and_then(Self::get_register_extend);
+
+        // Memory for large return values is reserved in the frame of the caller,
+        // its address is passed before the arguments.
+        let result = match return_type {
+            Some(return_type) if return_slots > Self::MAX_RETURN_SLOTS => {
+                let location =
+                    self.create_temporary(".result", &return_type, expression.pos.clone());
+
+                builder = Self::get_push_procedures(&return_type, &location, true)
+                    .into_iter()
+                    .fold(builder, |builder, kind| {
+                        builder.push(Procedure::new(expression.pos.clone(), kind))
+                    });
+                args.insert(0, 1);
+
+                Some((return_type, location))
+            }
+            _ => None,
+        };
+
+        let return_slots = if result.is_some() { 0 } else { return_slots };
+
+        builder = builder.append(arguments);
+        builder = match target {
+            CallTarget::Function(function_id) => builder.push(Procedure::new(
+                expression.pos.clone(),
+                ProcedureKind::ProcedureCall(ProcedureCall {
+                    function_id,
+                    args,
+                    return_slots,
+                }),
+            )),
+            CallTarget::Pointer(pointer) => builder.append(pointer).push(Procedure::new(
+                expression.pos.clone(),
+                ProcedureKind::PointerCall(PointerCall { args, return_slots }),
+            )),
+            CallTarget::Extern(identifier) => {
+                builder = builder.push(Procedure::new(
+                    expression.pos.clone(),
+                    ProcedureKind::SystemCall(SystemCall {
+                        identifier,
+                        args,
+                        return_slots,
+                    }),
+                ));
+
+                match extend {
+                    Some(extend) => builder.push(Procedure::new(
+                        expression.pos.clone(),
+                        ProcedureKind::Extend(extend),
+                    )),
+                    None => builder,
+                }
+            }
+        };
+
+        // The returned value has been written to the reserved memory
+        if let Some((return_type, location)) = result {
+            builder = Self::get_push_procedures(&return_type, &location, false)
+                .into_iter()
+                .fold(builder, |builder, kind| {
+                    builder.push(Procedure::new(expression.pos.clone(), kind))
+                });
+        }
+
+        Ok(builder)
+    }
+
+    /// Calls a function declared with `fn`, after checking the arguments.
+    fn handle_procedure_call(
+        &mut self,
+        expression: &Expression,
+        fcall: &FunctionCall,
+        function_id: usize,
+    ) -> Result<Builder, CompilerError> {
+        let function = &self.functions[function_id];
+        let return_type = function.return_type.clone();
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|par| (par.typ.clone(), par.alias.clone(), par.pos.clone()))
+            .collect::<Vec<_>>();
+
+        self.check_arguments(&fcall.args, &parameters, &fcall.args_pos)?;
+        let arguments = self.handle_arguments(&fcall.args, &parameters)?;
+
+        self.handle_call(
+            expression,
+            arguments,
+            Self::get_argument_slots(&parameters),
+            return_type,
+            CallTarget::Function(function_id),
+        )
+    }
 
-        for arg in &fcall.args {
-            let expr = self.handle_expression(arg)?;
-            builder = builder.append(expr);
+    /// Calls a function declared with `extern fn`, after checking the arguments.
+    /// Arguments after the parameters of a variadic function are passed as they are.
+    fn handle_extern_call(
+        &mut self,
+        expression: &Expression,
+        fcall: &FunctionCall,
+        identifier: String,
+    ) -> Result<Builder, CompilerError> {
+        let function = &self.extern_functions[&identifier];
+        let return_type = function.return_type.clone();
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|par| (par.typ.clone(), par.alias.clone(), par.pos.clone()))
+            .collect::<Vec<_>>();
+
+        let (args, variadic_args) = match function.variadic {
+            true if fcall.args.len() >= parameters.len() => fcall.args.split_at(parameters.len()),
+            _ => (&fcall.args[..], &[][..]),
+        };
+
+        self.check_arguments(args, &parameters, &fcall.args_pos)?;
+
+        let mut arguments = Builder::new();
+        let typed_args = args
+            .iter()
+            .zip(parameters.iter().map(|(typ, ..)| Some(typ)));
+        let variadic_args = variadic_args.iter().zip(std::iter::repeat(None));
+
+        for (arg, parameter) in typed_args.chain(variadic_args) {
+            let typ = match parameter {
+                Some(typ) => typ.clone(),
+                None => {
+                    let typ = Self::decay_type(self.infer_type(arg)?);
+                    Self::check_extern_type(&typ, &arg.pos, CompilerErrorKind::ExternArgument)?;
+                    typ
+                }
+            };
+
+            arguments = arguments.append(self.handle_argument(arg, parameter)?);
+
+            // C functions read the whole register of values smaller than it
+            if let Some(extend) = Self::get_register_extend(&typ) {
+                arguments = arguments.push(Procedure::new(
+                    arg.pos.clone(),
+                    ProcedureKind::Extend(extend),
+                ));
+            }
+        }
+
+        self.handle_call(
+            expression,
+            arguments,
+            vec![1; fcall.args.len()],
+            return_type,
+            CallTarget::Extern(identifier),
+        )
+    }
+
+    /// `value.method(args)` on a `&dyn Trait`, the method is read from the vtable.
+    fn handle_dyn_method_call(
+        &mut self,
+        expression: &Expression,
+        fcall: &FunctionCall,
+        access: &MemberAccess,
+        trait_identifier: &str,
+    ) -> Result<Builder, CompilerError> {
+        let method = self.get_dyn_method(&fcall.left, access, trait_identifier)?;
+
+        self.check_arguments(&fcall.args, &method.parameters, &fcall.args_pos)?;
+
+        let typ = self.infer_type(&access.left)?;
+        let receiver = self.create_temporary(".receiver", &typ, access.left.pos.clone());
+
+        let arguments = self
+            .handle_dyn_receiver(&access.left, &receiver)?
+            .append(self.handle_arguments(&fcall.args, &method.parameters)?);
+        let function = self.handle_dyn_function(&access.left, &receiver, method.index)?;
+
+        self.handle_call(
+            expression,
+            arguments,
+            [vec![1], Self::get_argument_slots(&method.parameters)].concat(),
+            method.return_type,
+            CallTarget::Pointer(function),
+        )
+    }
+
+    /// `value.method(args)`, where the value is passed as the `self` parameter.
+    /// A value is borrowed when the method takes a pointer, a pointer is dereferenced
+    /// when it takes a value.
+    fn handle_method_call(
+        &mut self,
+        expression: &Expression,
+        fcall: &FunctionCall,
+        access: &MemberAccess,
+    ) -> Result<Builder, CompilerError> {
+        if let VariableType::Dyn(trait_identifier) = self.infer_type(&access.left)?.without_const()
+        {
+            return self.handle_dyn_method_call(expression, fcall, access, &trait_identifier);
+        }
+
+        let function_id = self.get_method(&fcall.left, access)?;
+        let function = &self.functions[function_id];
+        let return_type = function.return_type.clone();
+
+        let Some((receiver, parameters)) = function.parameters.split_first() else {
+            unreachable!("Methods without a self parameter are rejected when declared");
+        };
+
+        let receiver_type = receiver.typ.clone();
+        let receiver_slots = Self::get_type_slots(&receiver_type);
+        let parameters = parameters
+            .iter()
+            .map(|par| (par.typ.clone(), par.alias.clone(), par.pos.clone()))
+            .collect::<Vec<_>>();
+
+        self.check_arguments(&fcall.args, &parameters, &fcall.args_pos)?;
+
+        let value_type = self.infer_type(&access.left)?;
+        let mut arguments = match (&receiver_type, &value_type) {
+            (VariableType::Pointer(_), VariableType::Pointer(_)) => {
+                self.handle_expression(&access.left)?
+            }
+            (VariableType::Pointer(_), _) => self.handle_ref(&access.left)?,
+            (_, VariableType::Pointer(_)) => self
+                .handle_dereferenced_pointer(&access.left)?
+                .append(Self::get_load(&receiver_type, &access.left.pos)),
+            _ => self.handle_expression(&access.left)?,
+        };
+
+        arguments = arguments.append(self.handle_arguments(&fcall.args, &parameters)?);
+
+        self.handle_call(
+            expression,
+            arguments,
+            [vec![receiver_slots], Self::get_argument_slots(&parameters)].concat(),
+            return_type,
+            CallTarget::Function(function_id),
+        )
+    }
+
+    /// `exit(status)` ends the program with the status, after flushing printed output.
+    fn check_exit_status(&self, fcall: &FunctionCall) -> Result<(), CompilerError> {
+        let [status] = fcall.args.as_slice() else {
+            return Err(CompilerError::new(
+                fcall.args_pos.clone(),
+                CompilerErrorKind::WrongArgumentCount {
+                    expected: 1,
+                    got: fcall.args.len(),
+                },
+            ));
+        };
+
+        let got = self.infer_type(status)?;
+        let expected = VariableType::Value(Keyword::Int);
+        if !self.is_coercible(&got, &expected) {
+            return Err(CompilerError::new(
+                status.pos.clone(),
+                CompilerErrorKind::WrongType {
+                    got,
+                    expected,
+                    expected_alias: None,
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn handle_function_call(
+        &mut self,
+        expression: &Expression,
+        fcall: &FunctionCall,
+    ) -> Result<Builder, CompilerError> {
+        if let ExpressionKind::MemberAccess(access) = &fcall.left.kind {
+            return self.handle_method_call(expression, fcall, access);
         }
 
         let identifier = fcall.left.get_string().unwrap().to_string();
+        if self.extern_functions.contains_key(&identifier) {
+            return self.handle_extern_call(expression, fcall, identifier);
+        }
+
         if BUILT_IN.contains(&identifier.as_str()) {
+            if identifier == "exit" {
+                self.check_exit_status(fcall)?;
+            }
+
+            let mut builder = Builder::new();
+            let mut args = Vec::new();
+            for arg in &fcall.args {
+                builder = builder.append(self.handle_argument(arg, None)?);
+                args.push(Self::get_type_slots(&Self::decay_type(
+                    self.infer_type(arg)?,
+                )));
+            }
+
             return Ok(builder.push(Procedure::new(
                 expression.pos.clone(),
                 ProcedureKind::SystemCall(SystemCall {
-                    nargs: fcall.args.len(),
+                    args,
                     identifier,
+                    return_slots: 0,
                 }),
             )));
         }
 
+        if self.generic_functions.contains_key(&identifier) {
+            let function_id = self.get_instance(&identifier, fcall)?;
+            return self.handle_procedure_call(expression, fcall, function_id);
+        }
+
         let Some(variable) = self.get_variable(&identifier) else {
             return Err(CompilerError::new(
                 fcall.left.pos.clone(),
@@ -41,45 +500,60 @@ impl Program {
             ));
         };
 
-        let VariableType::Function(function_id) = variable.typ else {
-            todo!("Variable is not a function")
-        };
-
-        let function = self.functions.get(function_id).unwrap();
+        match variable.typ.clone() {
+            VariableType::Function(function_id) => {
+                self.handle_procedure_call(expression, fcall, function_id)
+            }
+            VariableType::FunctionPointer(parameters, return_type) => {
+                // The parameters of a function pointer
+                // are only known from the variable declaration.
+                let parameters = parameters
+                    .into_iter()
+                    .map(|par_type| (par_type, None, variable.pos.clone()))
+                    .collect::<Vec<_>>();
 
-        if function.parameters.len() != fcall.args.len() {
-            todo!(
-                "Wrong number of arguments to function, takes {}, {} was given",
-                function.parameters.len(),
-                fcall.args.len()
-            )
-        }
+                self.check_arguments(&fcall.args, &parameters, &fcall.args_pos)?;
+                let arguments = self.handle_arguments(&fcall.args, &parameters)?;
 
-        for (par, arg) in function.parameters.iter().zip(&fcall.args) {
-            let arg_type = self.infer_type(arg)?;
-            let par_type = self.get_variable_type(&par.typ);
+                let pointer = self.handle_identifier(&fcall.left, &identifier, false)?;
 
-            if arg_type != par_type {
-                return Err(CompilerError::new(
-                    arg.pos.clone(),
-                    CompilerErrorKind::WrongArgumentType {
-                        parameter: par_type,
-                        argument: arg_type,
-                        parameter_pos: par.pos.clone(),
-                    },
-                ));
+                self.handle_call(
+                    expression,
+                    arguments,
+                    Self::get_argument_slots(&parameters),
+                    return_type.map(|return_type| *return_type),
+                    CallTarget::Pointer(pointer),
+                )
             }
+            _ => todo!("Variable is not a function"),
         }
+    }
 
-        builder = builder.push(Procedure::new(
-            expression.pos.clone(),
-            ProcedureKind::ProcedureCall(ProcedureCall {
-                function_id,
-                nargs: fcall.args.len(),
-                returns: function.return_type.is_some(),
-            }),
-        ));
+    /// Arrays are passed as a pointer to their first element.
+    /// Built in functions have no parameter types to coerce the argument to.
+    fn handle_argument(
+        &mut self,
+        arg: &Expression,
+        parameter: Option<&VariableType>,
+    ) -> Result<Builder, CompilerError> {
+        match (self.infer_type(arg)?, parameter) {
+            (VariableType::Array(..), _) => self.handle_ref(arg),
+            (_, Some(parameter)) => self.handle_coerced_expression(arg, parameter),
+            (_, None) => self.handle_expression(arg),
+        }
+    }
 
-        Ok(builder)
+    /// Pushes the arguments, coerced to the types of the parameters.
+    fn handle_arguments(
+        &mut self,
+        args: &[Expression],
+        parameters: &[Parameter],
+    ) -> Result<Builder, CompilerError> {
+        let mut arguments = Builder::new();
+        for (arg, (par_type, ..)) in args.iter().zip(parameters) {
+            arguments = arguments.append(self.handle_argument(arg, Some(par_type))?);
+        }
+
+        Ok(arguments)
     }
 }
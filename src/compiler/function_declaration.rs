@@ -1,49 +1,141 @@
+use std::{collections::HashMap, ops::Range};
+
 use crate::parser::definition::{Declaration, FunctionDeclaration};
 
 use super::{
     builder::Builder,
-    definition::{Function, Procedure, ProcedureKind},
+    definition::{
+        ArgumentLocation, Assign, Function, FunctionParameter, OperandValue, Procedure,
+        ProcedureKind,
+    },
     error::CompilerError,
     program::Program,
     scope::{
-        variable::{Variable, VariableType},
+        variable::{Variable, VariableLocation, VariableType},
         ProgramScope,
     },
 };
 
 impl Program {
-    pub fn handle_function_declaration(
+    /// Resolves the types of the parameters and the return type,
+    /// with the given types for `Self` and the type parameters.
+    pub fn get_function_signature(
+        &self,
+        fdec: &FunctionDeclaration,
+        type_parameters: &HashMap<String, VariableType>,
+    ) -> Result<(Vec<FunctionParameter>, Option<VariableType>), CompilerError> {
+        let parameters = fdec
+            .parameters
+            .iter()
+            .map(|parameter| {
+                Ok(FunctionParameter {
+                    identifier: parameter.identifier.clone(),
+                    mutable: parameter.mutable,
+                    typ: self.get_generic_type(&parameter.typ, type_parameters)?,
+                    alias: self.get_type_alias(&parameter.typ),
+                    pos: parameter.pos.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, CompilerError>>()?;
+
+        let return_type = fdec
+            .return_type
+            .as_ref()
+            .map(|t| self.get_generic_type(t, type_parameters))
+            .transpose()?;
+
+        Ok((parameters, return_type))
+    }
+
+    /// Compiles the content of a function with the given signature.
+    pub fn get_function_body(
         &mut self,
-        statement: &Declaration,
+        pos: &Range<usize>,
         fdec: &FunctionDeclaration,
+        parameters: &[FunctionParameter],
+        return_type: &Option<VariableType>,
     ) -> Result<Builder, CompilerError> {
         if let ProgramScope::FunctionScope(_) = &mut self.scope {
             todo!("We're already in a function");
         };
 
         let old_stack_pos = self.stack_pos;
-        let return_type = fdec.return_type.as_ref().map(|t| self.get_variable_type(t));
 
         let body = self.with_function_scope(return_type.clone(), |this| {
-            let parameters = fdec
-                .parameters
-                .iter()
-                .rev()
-                .map(|parameter| {
+            // Large values are returned through a hidden pointer, passed as the first argument
+            let return_pointer = match return_type {
+                Some(typ) if Self::get_type_slots(typ) > Self::MAX_RETURN_SLOTS => Some((
+                    ".return_pointer".to_string(),
+                    Variable {
+                        pos: pos.clone(),
+                        typ: VariableType::Pointer(Box::new(typ.clone())),
+                        mutable: false,
+                    },
+                )),
+                _ => None,
+            };
+
+            let has_return_pointer = return_pointer.is_some();
+            let parameters = return_pointer
+                .into_iter()
+                .chain(parameters.iter().map(|parameter| {
                     (
                         parameter.identifier.clone(),
-                        parameter.pos.clone(),
-                        this.get_variable_type(&parameter.typ),
+                        Variable {
+                            pos: parameter.pos.clone(),
+                            typ: parameter.typ.clone(),
+                            mutable: parameter.mutable,
+                        },
                     )
-                })
+                }))
+                .collect::<Vec<_>>();
+
+            let slots = parameters
+                .iter()
+                .map(|(_, variable)| Self::get_type_slots(&variable.typ))
                 .collect::<Vec<_>>();
+            let locations = Self::get_argument_locations(&slots);
 
             let ProgramScope::FunctionScope(function_scope) = &mut this.scope else {
                 unreachable!();
             };
 
-            for (identifier, pos, typ) in parameters {
-                function_scope.create_parameter(identifier, Variable { pos, typ });
+            function_scope.return_type_pos = fdec.return_type.as_ref().map(|typ| typ.pos.clone());
+
+            // Parameters passed in registers are stored in the frame, before they are overwritten.
+            // The first register holds the first 8 bytes, which are pushed first.
+            let mut spill = Builder::new();
+
+            for ((identifier, variable), location) in parameters.into_iter().zip(locations) {
+                let slots = Self::get_type_slots(&variable.typ);
+                let size = Self::get_type_size(&variable.typ);
+
+                let ArgumentLocation::Registers(register) = location else {
+                    function_scope.create_parameter(identifier, variable, slots, true);
+                    continue;
+                };
+
+                let location = function_scope.create_parameter(identifier, variable, slots, false);
+
+                if has_return_pointer && register == 0 {
+                    let VariableLocation::Stack(loc) = location else {
+                        unreachable!();
+                    };
+
+                    function_scope.return_pointer = Some(loc);
+                }
+
+                for slot in 0..slots {
+                    spill = spill.push(Procedure::new(
+                        pos.clone(),
+                        ProcedureKind::Push(OperandValue::ArgumentRegister(register + slot)),
+                    ));
+                }
+
+                spill = spill.push(Procedure::new(
+                    pos.clone(),
+                    ProcedureKind::Assign(Assign { location, size }),
+                ));
             }
 
             // Calling a function adds the RET address to the stack,
@@ -52,6 +144,10 @@ impl Program {
 
             let procedures = this.get_procedures(&fdec.content)?;
 
+            if let Some(typ) = return_type {
+                Self::check_returns(&fdec.content, &fdec.identifier_pos, typ)?;
+            }
+
             let ProgramScope::FunctionScope(function_scope) = &mut this.scope else {
                 unreachable!();
             };
@@ -64,19 +160,37 @@ impl Program {
 
             let builder = Builder::new()
                 .push(Procedure::new(
-                    statement.pos.clone(),
+                    pos.clone(),
                     ProcedureKind::Allocate(variable_sizes),
                 ))
-                .append(procedures);
+                .append(spill)
+                .append(procedures)
+                .append(function_scope.scope.get_deferred());
 
             Ok(builder)
         })?;
 
+        self.stack_pos = old_stack_pos;
+
+        Ok(body)
+    }
+
+    pub fn handle_function_declaration(
+        &mut self,
+        statement: &Declaration,
+        fdec: &FunctionDeclaration,
+    ) -> Result<Builder, CompilerError> {
+        Self::check_not_built_in(&fdec.identifier, &fdec.identifier_pos)?;
+
+        let (parameters, return_type) = self.get_function_signature(fdec, &self.type_parameters)?;
+        let body = self.get_function_body(&statement.pos, fdec, &parameters, &return_type)?;
+
         self.functions.push(Function {
             body,
-            parameters: fdec.parameters.clone(),
+            parameters,
             return_type,
             identifier_pos: fdec.identifier_pos.clone(),
+            export: fdec.exported.then(|| fdec.identifier.clone()),
         });
 
         let function_location = self.functions.len() - 1;
@@ -87,11 +201,10 @@ impl Program {
             Variable {
                 pos: statement.pos.clone(),
                 typ: VariableType::Function(function_location),
+                mutable: false,
             },
         );
 
-        self.stack_pos = old_stack_pos;
-
         Ok(Builder::new())
     }
 }
@@ -0,0 +1,279 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    lexer::Keyword,
+    parser::{
+        definition::{Declaration, DeclarationKind, FunctionCall, GenericParameter},
+        types::{Type, TypeIdentifier},
+    },
+};
+
+use super::{
+    builder::Builder,
+    definition::{Function, GenericFunction, Instance},
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::{TypeParameter, VariableType},
+};
+
+/// Generic functions are monomorphized, every combination of type arguments
+/// a generic function is called with is compiled to a function of its own.
+impl Program {
+    /// Generic functions are compiled when they are called,
+    /// so they can be called before they are declared.
+    /// Generic functions cannot be exported,
+    /// as they are compiled for every type they are called with.
+    pub fn register_generic_function(
+        &mut self,
+        declaration: Declaration,
+    ) -> Result<(), CompilerError> {
+        let DeclarationKind::FunctionDeclaration(fdec) = declaration.kind else {
+            unreachable!("Only functions are generic");
+        };
+
+        if fdec.exported {
+            return Err(CompilerError::new(
+                fdec.identifier_pos.clone(),
+                CompilerErrorKind::ExportedGenericFunction,
+            ));
+        }
+
+        Self::check_not_built_in(&fdec.identifier, &fdec.identifier_pos)?;
+
+        self.generic_functions.insert(
+            fdec.identifier.clone(),
+            GenericFunction {
+                pos: declaration.pos,
+                declaration: Rc::new(fdec),
+                instances: Vec::new(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Finds the type parameters of `typ` by matching it against the type of an argument.
+    fn bind_type_parameters(
+        typ: &Type,
+        argument: &VariableType,
+        generics: &[GenericParameter],
+        bindings: &mut HashMap<String, VariableType>,
+    ) {
+        let children = typ.children.iter().flatten();
+
+        match (&typ.identifier, argument) {
+            (TypeIdentifier::Struct(identifier), _)
+                if generics
+                    .iter()
+                    .any(|generic| &generic.identifier == identifier) =>
+            {
+                // The first argument decides, later arguments are type checked against it
+                bindings
+                    .entry(identifier.clone())
+                    .or_insert_with(|| argument.clone());
+            }
+            (TypeIdentifier::Primitive(Keyword::Pointer), VariableType::Pointer(inner))
+            | (
+                TypeIdentifier::Nullable,
+                VariableType::Pointer(inner) | VariableType::Nullable(inner),
+            )
+            | (TypeIdentifier::Primitive(Keyword::Const), VariableType::Const(inner))
+            | (TypeIdentifier::Array(_), VariableType::Array(inner, _))
+            | (TypeIdentifier::Slice, VariableType::Slice(inner)) => {
+                for child in children {
+                    Self::bind_type_parameters(child, inner, generics, bindings);
+                }
+            }
+            // A pointer to a mutable value may be passed as a pointer to const
+            (TypeIdentifier::Primitive(Keyword::Const), _) => {
+                for child in children {
+                    Self::bind_type_parameters(child, argument, generics, bindings);
+                }
+            }
+            (TypeIdentifier::Tuple, VariableType::Tuple(types)) => {
+                for (child, typ) in children.zip(types) {
+                    Self::bind_type_parameters(child, typ, generics, bindings);
+                }
+            }
+            (
+                TypeIdentifier::Function(return_type),
+                VariableType::FunctionPointer(types, returned),
+            ) => {
+                for (child, typ) in children.zip(types) {
+                    Self::bind_type_parameters(child, typ, generics, bindings);
+                }
+
+                if let (Some(return_type), Some(returned)) = (return_type, returned) {
+                    Self::bind_type_parameters(return_type, returned, generics, bindings);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the type has an `impl` of the trait.
+    pub fn implements(&self, typ: &VariableType, trait_identifier: &str) -> bool {
+        match typ {
+            VariableType::Struct(struct_type) => self.structs[struct_type.id]
+                .traits
+                .iter()
+                .any(|identifier| identifier == trait_identifier),
+            _ => false,
+        }
+    }
+
+    /// Infers the type arguments of a call to a generic function from the arguments,
+    /// and checks that they implement the traits they are bound by.
+    pub fn get_type_arguments(
+        &self,
+        generic: &GenericFunction,
+        fcall: &FunctionCall,
+    ) -> Result<HashMap<String, VariableType>, CompilerError> {
+        let fdec = &generic.declaration;
+        let mut bindings = HashMap::new();
+
+        for (parameter, arg) in fdec.parameters.iter().zip(&fcall.args) {
+            let arg_type = Self::decay_type(self.infer_type(arg)?);
+            Self::bind_type_parameters(&parameter.typ, &arg_type, &fdec.generics, &mut bindings);
+        }
+
+        for generic_parameter in &fdec.generics {
+            let Some(typ) = bindings.get(&generic_parameter.identifier) else {
+                return Err(CompilerError::new(
+                    fcall.left.pos.clone(),
+                    CompilerErrorKind::UninferredTypeParameter {
+                        parameter: generic_parameter.identifier.clone(),
+                        parameter_pos: generic_parameter.identifier_pos.clone(),
+                    },
+                ));
+            };
+
+            for (bound, bound_pos) in &generic_parameter.bounds {
+                if !self.traits.contains_key(bound) {
+                    return Err(CompilerError::new(
+                        bound_pos.clone(),
+                        CompilerErrorKind::UndefinedTrait(bound.clone()),
+                    ));
+                }
+
+                if !self.implements(typ, bound) {
+                    return Err(CompilerError::new(
+                        fcall.args_pos.clone(),
+                        CompilerErrorKind::UnsatisfiedBound {
+                            typ: typ.clone(),
+                            trait_identifier: bound.clone(),
+                            bound_pos: bound_pos.clone(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// The id of the function compiled for the type arguments of the call.
+    /// The function is only compiled once for the same type arguments, after the rest of the program.
+    pub fn get_instance(
+        &mut self,
+        identifier: &str,
+        fcall: &FunctionCall,
+    ) -> Result<usize, CompilerError> {
+        let generic = &self.generic_functions[identifier];
+        let type_parameters = self.get_type_arguments(generic, fcall)?;
+
+        let type_arguments = generic
+            .declaration
+            .generics
+            .iter()
+            .map(|parameter| type_parameters[&parameter.identifier].clone())
+            .collect::<Vec<_>>();
+
+        if let Some((_, function_id)) = generic
+            .instances
+            .iter()
+            .find(|(arguments, _)| *arguments == type_arguments)
+        {
+            return Ok(*function_id);
+        }
+
+        let declaration = generic.declaration.clone();
+        let pos = generic.pos.clone();
+        let (parameters, return_type) =
+            self.get_function_signature(&declaration, &type_parameters)?;
+
+        // The body is compiled later, so the function can already be called recursively
+        self.functions.push(Function {
+            body: Builder::new(),
+            parameters,
+            return_type,
+            identifier_pos: declaration.identifier_pos.clone(),
+            export: None,
+        });
+
+        let function_id = self.functions.len() - 1;
+
+        self.generic_functions
+            .get_mut(identifier)
+            .unwrap()
+            .instances
+            .push((type_arguments, function_id));
+
+        self.instances.push(Instance {
+            function_id,
+            pos,
+            declaration,
+            type_parameters,
+        });
+
+        Ok(function_id)
+    }
+
+    /// Marks the struct type arguments with the type parameter they were passed as,
+    /// so that the body can only call the methods of its bounds on them.
+    fn mark_type_arguments(
+        generics: &[GenericParameter],
+        mut type_parameters: HashMap<String, VariableType>,
+    ) -> HashMap<String, VariableType> {
+        for generic in generics {
+            if let Some(VariableType::Struct(struct_type)) =
+                type_parameters.get_mut(&generic.identifier)
+            {
+                struct_type.type_parameter = Some(Rc::new(TypeParameter {
+                    identifier: generic.identifier.clone(),
+                    pos: generic.identifier_pos.clone(),
+                    bounds: generic
+                        .bounds
+                        .iter()
+                        .map(|(bound, _)| bound.clone())
+                        .collect(),
+                }));
+            }
+        }
+
+        type_parameters
+    }
+
+    /// Compiles the bodies of the called instances, which may call further instances.
+    pub fn compile_instances(&mut self) -> Result<(), CompilerError> {
+        while let Some(instance) = self.instances.pop() {
+            let type_parameters =
+                Self::mark_type_arguments(&instance.declaration.generics, instance.type_parameters);
+            let (parameters, return_type) =
+                self.get_function_signature(&instance.declaration, &type_parameters)?;
+
+            let body = self.with_type_parameters(type_parameters, |this| {
+                this.get_function_body(
+                    &instance.pos,
+                    &instance.declaration,
+                    &parameters,
+                    &return_type,
+                )
+            })?;
+
+            self.functions[instance.function_id].body = body;
+        }
+
+        Ok(())
+    }
+}
@@ -16,29 +16,65 @@ impl Program {
     pub fn handle_if_statement(
         &mut self,
         statement: &Statement,
-        if_statements: &Vec<IfStatement>,
+        if_statements: &[IfStatement],
     ) -> Result<Builder, CompilerError> {
         let mut ifs = Vec::new();
 
-        for if_statement in if_statements {
+        // Without an else branch, it's possible to not enter any of the branches
+        let exhaustive = if_statements
+            .last()
+            .is_some_and(|if_statement| if_statement.condition.is_none());
+
+        // Pointers are known to not be null after the branches,
+        // if they were before and in the end of every branch.
+        // Pointers compared with `== null` are not null in the following branches.
+        let mut after = self.get_non_null();
+        let mut not_null = after.clone();
+
+        self.with_branches(if_statements, exhaustive, |this, if_statement| {
+            this.set_non_null(not_null.clone());
+
+            let comparison = if_statement
+                .condition
+                .as_ref()
+                .and_then(|condition| this.get_null_comparison(condition));
+
             let condition = if_statement
                 .condition
                 .as_ref()
-                .map_or(Ok(None), |condition| match self.infer_type(condition)? {
+                .map_or(Ok(None), |condition| match this.infer_type(condition)? {
                     VariableType::Value(Keyword::Bool) => {
-                        Ok(Some(self.handle_expression(condition)?))
+                        Ok(Some(this.handle_expression(condition)?))
                     }
                     _ => todo!("error"),
                 })?
                 .map(Box::new);
 
-            let content = self.with_scope(|this| this.get_procedures(&if_statement.content))?;
+            match comparison {
+                Some((loc, true)) => {
+                    not_null.insert(loc);
+                }
+                Some((loc, false)) => {
+                    let mut inside = not_null.clone();
+                    inside.insert(loc);
+                    this.set_non_null(inside);
+                }
+                None => {}
+            }
+
+            let content = this.with_scope(|this| this.get_procedures(&if_statement.content))?;
+            let end = this.get_non_null();
+            after.retain(|loc| end.contains(loc));
 
             ifs.push(If {
                 condition,
                 content: Box::new(content),
             });
-        }
+
+            Ok(())
+        })?;
+
+        self.set_non_null(after);
 
         let builder = Builder::new().push(Procedure::new(
             statement.pos.clone(),
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use crate::parser::definition::{
+    DeclarationKind, Expression, FunctionDeclaration, ImplDeclaration, MemberAccess, TraitMethod,
+};
+
+use super::{
+    builder::Builder,
+    definition::Function,
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::{variable::VariableType, ProgramScope},
+};
+
+impl Program {
+    /// The function id of the method called on the value of the member access.
+    /// Methods of a struct can also be called through a pointer to it.
+    pub fn get_method(
+        &self,
+        expression: &Expression,
+        access: &MemberAccess,
+    ) -> Result<usize, CompilerError> {
+        let strip_const = |typ| match typ {
+            VariableType::Const(inner) => *inner,
+            typ => typ,
+        };
+
+        let typ = match strip_const(self.infer_type(&access.left)?) {
+            VariableType::Pointer(inner) => strip_const(*inner),
+            typ => typ,
+        };
+
+        Self::check_not_nullable(access.left.pos.clone(), &typ)?;
+
+        if let VariableType::Struct(struct_type) = &typ {
+            // A type parameter only has the methods of the traits it is bound by
+            if let Some(parameter) = &struct_type.type_parameter {
+                let bound = parameter.bounds.iter().any(|bound| {
+                    self.traits[bound]
+                        .methods
+                        .iter()
+                        .any(|method| method.identifier == access.member)
+                });
+
+                if !bound {
+                    return Err(CompilerError::new(
+                        expression.pos.clone(),
+                        CompilerErrorKind::UnboundMethod {
+                            parameter: parameter.identifier.clone(),
+                            method: access.member.clone(),
+                            parameter_pos: parameter.pos.clone(),
+                        },
+                    ));
+                }
+            }
+
+            if let Some(function_id) = self.structs[struct_type.id].methods.get(&access.member) {
+                return Ok(*function_id);
+            }
+        }
+
+        Err(CompilerError::new(
+            expression.pos.clone(),
+            CompilerErrorKind::UndefinedMethod {
+                typ,
+                method: access.member.clone(),
+            },
+        ))
+    }
+
+    /// The type of the trait method for the implementing type, as a function pointer.
+    fn get_trait_method_type(
+        &self,
+        method: &TraitMethod,
+        type_parameters: &HashMap<String, VariableType>,
+    ) -> Result<VariableType, CompilerError> {
+        Ok(VariableType::FunctionPointer(
+            method
+                .parameters
+                .iter()
+                .map(|parameter| self.get_generic_type(&parameter.typ, type_parameters))
+                .collect::<Result<_, _>>()?,
+            method
+                .return_type
+                .as_ref()
+                .map(|return_type| self.get_generic_type(return_type, type_parameters))
+                .transpose()?
+                .map(Box::new),
+        ))
+    }
+
+    /// Checks the method against the declaration in the trait.
+    fn check_trait_method(
+        &self,
+        trait_identifier: &str,
+        fdec: &FunctionDeclaration,
+        function: &Function,
+        type_parameters: &HashMap<String, VariableType>,
+    ) -> Result<(), CompilerError> {
+        let trait_declaration = &self.traits[trait_identifier];
+
+        let Some(method) = trait_declaration
+            .methods
+            .iter()
+            .find(|method| method.identifier == fdec.identifier)
+        else {
+            return Err(CompilerError::new(
+                fdec.identifier_pos.clone(),
+                CompilerErrorKind::UnknownTraitMethod {
+                    trait_identifier: trait_identifier.to_string(),
+                    method: fdec.identifier.clone(),
+                    trait_pos: trait_declaration.identifier_pos.clone(),
+                },
+            ));
+        };
+
+        let expected = self.get_trait_method_type(method, type_parameters)?;
+        let got = VariableType::FunctionPointer(
+            function
+                .parameters
+                .iter()
+                .map(|parameter| parameter.typ.clone())
+                .collect(),
+            function.return_type.clone().map(Box::new),
+        );
+
+        if got != expected {
+            return Err(CompilerError::new(
+                fdec.identifier_pos.clone(),
+                CompilerErrorKind::WrongMethodSignature {
+                    method: fdec.identifier.clone(),
+                    got,
+                    expected,
+                    expected_pos: method.identifier_pos.clone(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Methods are compiled like functions, with `Self` being the implementing type.
+    /// They are not variables, but are looked up on the struct when called.
+    pub fn handle_impl_declaration(
+        &mut self,
+        idec: &ImplDeclaration,
+    ) -> Result<Builder, CompilerError> {
+        let ProgramScope::RootScope(_) = &mut self.scope else {
+            return Err(CompilerError::new(
+                idec.typ.pos.clone(),
+                CompilerErrorKind::NestedDeclaration("Methods"),
+            ));
+        };
+
+        let typ = self.get_variable_type(&idec.typ)?;
+        let VariableType::Struct(struct_type) = &typ else {
+            return Err(CompilerError::new(
+                idec.typ.pos.clone(),
+                CompilerErrorKind::ExpectedStruct(typ),
+            ));
+        };
+
+        let struct_id = struct_type.id;
+        let type_parameters = HashMap::from([("Self".to_string(), typ.clone())]);
+
+        let methods = idec
+            .methods
+            .iter()
+            .map(|declaration| match &declaration.kind {
+                DeclarationKind::FunctionDeclaration(fdec) => (declaration, fdec),
+                _ => unreachable!("Only methods are parsed in impl blocks"),
+            })
+            .collect::<Vec<_>>();
+
+        if let Some((trait_identifier, trait_pos)) = &idec.trait_identifier {
+            let Some(trait_declaration) = self.traits.get(trait_identifier) else {
+                return Err(CompilerError::new(
+                    trait_pos.clone(),
+                    CompilerErrorKind::UndefinedTrait(trait_identifier.clone()),
+                ));
+            };
+
+            if let Some(method) = trait_declaration.methods.iter().find(|method| {
+                !methods
+                    .iter()
+                    .any(|(_, fdec)| fdec.identifier == method.identifier)
+            }) {
+                return Err(CompilerError::new(
+                    trait_pos.clone(),
+                    CompilerErrorKind::MissingTraitMethod {
+                        trait_identifier: trait_identifier.clone(),
+                        method: method.identifier.clone(),
+                        typ,
+                        method_pos: method.identifier_pos.clone(),
+                    },
+                ));
+            }
+
+            // Methods may already rely on the trait being implemented
+            self.structs[struct_id]
+                .traits
+                .push(trait_identifier.clone());
+        }
+
+        for (declaration, fdec) in methods {
+            if !fdec.generics.is_empty() {
+                return Err(CompilerError::new(
+                    fdec.identifier_pos.clone(),
+                    CompilerErrorKind::GenericMethod(fdec.identifier.clone()),
+                ));
+            }
+
+            // Methods are only called on values, which are passed as `self`
+            if fdec
+                .parameters
+                .first()
+                .is_none_or(|parameter| parameter.identifier != "self")
+            {
+                return Err(CompilerError::new(
+                    fdec.identifier_pos.clone(),
+                    CompilerErrorKind::MissingSelfParameter(fdec.identifier.clone()),
+                ));
+            }
+
+            let (parameters, return_type) = self.get_function_signature(fdec, &type_parameters)?;
+            let function = Function {
+                body: Builder::new(),
+                parameters,
+                return_type,
+                identifier_pos: fdec.identifier_pos.clone(),
+                export: None,
+            };
+
+            if let Some((trait_identifier, _)) = &idec.trait_identifier {
+                self.check_trait_method(trait_identifier, fdec, &function, &type_parameters)?;
+            }
+
+            if let Some(first) = self.structs[struct_id].methods.get(&fdec.identifier) {
+                return Err(CompilerError::new(
+                    fdec.identifier_pos.clone(),
+                    CompilerErrorKind::MethodAlreadyDefined {
+                        typ: typ.clone(),
+                        method: fdec.identifier.clone(),
+                        first_pos: self.functions[*first].identifier_pos.clone(),
+                    },
+                ));
+            }
+
+            // Added before the body is compiled, so the method can call itself
+            self.functions.push(function);
+            let function_id = self.functions.len() - 1;
+            self.structs[struct_id]
+                .methods
+                .insert(fdec.identifier.clone(), function_id);
+
+            let function = &self.functions[function_id];
+            let parameters = function.parameters.clone();
+            let return_type = function.return_type.clone();
+
+            let body = self.with_type_parameters(type_parameters.clone(), |this| {
+                this.get_function_body(&declaration.pos, fdec, &parameters, &return_type)
+            })?;
+
+            self.functions[function_id].body = body;
+        }
+
+        Ok(Builder::new())
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    compiler::definition::{Arithmetic, OperandValue, Procedure, ProcedureKind},
+    compiler::{
+        definition::{Arithmetic, OperandValue, Procedure, ProcedureKind},
+        scope::variable::VariableType,
+    },
     parser::definition::{Expression, MemberAccess},
 };
 
@@ -11,15 +14,19 @@ impl Program {
         expression: &Expression,
         access: &MemberAccess,
     ) -> Result<Builder, CompilerError> {
-        let field_offset = self
-            .get_struct_field_type(&access.left, &access.member)?
-            .offset as i32;
+        let field_offset = self.get_struct_field_type(expression, access)?.offset as i32;
+
+        // The address of the struct is the value of a pointer to it
+        let address = match self.is_auto_deref(access)? {
+            true => self.handle_dereferenced_pointer(&access.left)?,
+            false => self.handle_ref(&access.left)?,
+        };
 
         let builder = Builder::new()
-            .append(self.handle_ref(&access.left)?)
+            .append(address)
             .push(Procedure::new(
                 expression.pos.clone(),
-                ProcedureKind::Push(OperandValue::Int(-field_offset)),
+                ProcedureKind::Push(OperandValue::Int(field_offset)),
             ))
             .push(Procedure::new(
                 expression.pos.clone(),
@@ -34,9 +41,17 @@ impl Program {
         expression: &Expression,
         access: &MemberAccess,
     ) -> Result<Builder, CompilerError> {
+        self.check_member_access_assigned(&access.left)?;
+
+        if let VariableType::Slice(_) = self.infer_type(&access.left)? {
+            self.check_slice_member(expression, access)?;
+            return self.handle_slice_length(&access.left);
+        }
+
+        let typ = self.infer_type(expression)?;
         let builder = self
             .handle_member_access_without_deref(expression, access)?
-            .push(Procedure::new(expression.pos.clone(), ProcedureKind::Deref));
+            .append(Self::get_load(&typ, &expression.pos));
 
         Ok(builder)
     }
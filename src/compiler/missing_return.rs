@@ -0,0 +1,80 @@
+use std::ops::Range;
+
+use crate::{
+    lexer::Literal,
+    parser::definition::{
+        Declaration, DeclarationKind, Expression, ExpressionKind, Primary, StatementKind,
+    },
+};
+
+use super::{
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::VariableType,
+};
+
+/// Missing return analysis.
+///
+/// A function with a return type has to end every path through its body with a `return`.
+/// `if` statements return on all paths if they have an `else` branch and every branch returns,
+/// `while` loops only if their condition is `true`, as there is no other way to leave them.
+/// Calling `exit` ends the program, so it does not need to be followed by a `return`.
+impl Program {
+    /// Errors at the code where a path through the body of the function may end.
+    pub fn check_returns(
+        content: &[Declaration],
+        pos: &Range<usize>,
+        typ: &VariableType,
+    ) -> Result<(), CompilerError> {
+        match Self::get_missing_return(content, pos) {
+            Some(pos) => Err(CompilerError::new(
+                pos,
+                CompilerErrorKind::MissingReturn(typ.clone()),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// The last statement of a path through the declarations which ends without returning,
+    /// or `pos` if they are empty.
+    fn get_missing_return(content: &[Declaration], pos: &Range<usize>) -> Option<Range<usize>> {
+        let mut missing = pos.clone();
+
+        for declaration in content {
+            let DeclarationKind::Statement(statement) = &declaration.kind else {
+                continue;
+            };
+
+            missing = match &statement.kind {
+                StatementKind::ReturnStatement(_) => return None,
+                StatementKind::Expression(expression) if Self::is_exit_call(expression) => {
+                    return None
+                }
+                StatementKind::IfStatements(ifs) if ifs.iter().any(|i| i.condition.is_none()) => {
+                    ifs.iter()
+                        .find_map(|i| Self::get_missing_return(&i.content, &statement.pos))?
+                }
+                StatementKind::WhileStatement(while_statement) => {
+                    if let ExpressionKind::Primary(Primary::Literal(Literal::Bool(true))) =
+                        while_statement.condition.kind
+                    {
+                        return None;
+                    }
+
+                    statement.pos.clone()
+                }
+                StatementKind::IfStatements(_) => statement.pos.clone(),
+                _ => statement.pos.clone(),
+            };
+        }
+
+        Some(missing)
+    }
+
+    fn is_exit_call(expression: &Expression) -> bool {
+        matches!(
+            &expression.kind,
+            ExpressionKind::FunctionCall(fcall) if fcall.left.get_string() == Some("exit")
+        )
+    }
+}
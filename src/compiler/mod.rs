@@ -8,20 +8,43 @@ pub mod program;
 pub mod scope;
 
 mod array_index;
+mod array_literal;
 mod assignment;
+mod cast;
 mod declaration;
+mod defer_statement;
+mod definite_assignment;
 mod error;
 mod expression;
+mod extern_function;
 mod function_call;
 mod function_declaration;
+mod generic;
 mod if_statement;
+mod impl_declaration;
 mod member_access;
+mod missing_return;
+mod nullable;
 mod return_statement;
+mod slice;
+mod static_data;
 mod struct_construction;
 mod struct_declaration;
+mod trait_declaration;
+mod tuple;
+mod type_alias;
+mod type_info;
 mod util;
+mod vtable;
 mod while_statement;
 
-pub fn compile(parsed: Vec<Declaration>) -> Result<Program, CompilerError> {
-    Program::new().compile(parsed)
+pub fn compile(
+    parsed: Vec<Declaration>,
+    checked: bool,
+    library: bool,
+) -> Result<Program, CompilerError> {
+    Program::new()
+        .with_checks(checked)
+        .with_library(library)
+        .compile(parsed)
 }
@@ -0,0 +1,135 @@
+use std::{collections::HashSet, ops::Range};
+
+use crate::{
+    lexer::{Keyword, Literal},
+    parser::definition::{Expression, ExpressionKind, Primary},
+};
+
+use super::{
+    builder::Builder,
+    definition::{Procedure, ProcedureKind},
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::{
+        variable::{StoredVariable, VariableLocation, VariableType},
+        ProgramScope,
+    },
+};
+
+/// Null checks.
+///
+/// A `pointer?<T>` variable is read as a `pointer<T>` where it is known to not be null,
+/// in `if (p != null)`, in the branches after `if (p == null)` and in `while (p != null)`.
+/// Such variables are tracked in `FunctionScope::non_null`,
+/// until they are assigned a value which may be null.
+impl Program {
+    pub fn get_non_null(&self) -> HashSet<isize> {
+        match &self.scope {
+            ProgramScope::FunctionScope(scope) => scope.non_null.clone(),
+            ProgramScope::RootScope(_) => HashSet::new(),
+        }
+    }
+
+    pub fn set_non_null(&mut self, non_null: HashSet<isize>) {
+        if let ProgramScope::FunctionScope(scope) = &mut self.scope {
+            scope.non_null = non_null;
+        }
+    }
+
+    /// The variable might have been set to null, for example through a pointer to it.
+    pub fn mark_nullable(&mut self, location: &VariableLocation) {
+        if let (ProgramScope::FunctionScope(scope), VariableLocation::Stack(loc)) =
+            (&mut self.scope, location)
+        {
+            scope.non_null.remove(loc);
+        }
+    }
+
+    /// Whether the variable may be null after it has been assigned a value of the type.
+    pub fn mark_assigned_type(&mut self, location: &VariableLocation, typ: &VariableType) {
+        match (&mut self.scope, location, typ) {
+            (
+                ProgramScope::FunctionScope(scope),
+                VariableLocation::Stack(loc),
+                VariableType::Pointer(_),
+            ) => {
+                scope.non_null.insert(*loc);
+            }
+            _ => self.mark_nullable(location),
+        }
+    }
+
+    /// The type the variable is read as at this point in the code.
+    pub fn get_narrowed_type(&self, variable: &StoredVariable) -> VariableType {
+        match (&self.scope, &variable.typ, &variable.location) {
+            (
+                ProgramScope::FunctionScope(scope),
+                VariableType::Nullable(inner),
+                VariableLocation::Stack(loc),
+            ) if scope.non_null.contains(loc) => VariableType::Pointer(inner.clone()),
+            (_, typ, _) => typ.clone(),
+        }
+    }
+
+    /// The stack position of the `pointer?<T>` variable compared to null in the condition,
+    /// and whether the condition holds when it is null.
+    pub fn get_null_comparison(&self, condition: &Expression) -> Option<(isize, bool)> {
+        let ExpressionKind::Binary(binary) = &condition.kind else {
+            return None;
+        };
+
+        let is_null = match binary.operator {
+            Keyword::Equality => true,
+            Keyword::NotEqual => false,
+            _ => return None,
+        };
+
+        let identifier = match (&binary.left.kind, &binary.right.kind) {
+            (
+                ExpressionKind::Primary(Primary::Identifier(identifier)),
+                ExpressionKind::Primary(Primary::Literal(Literal::Null)),
+            )
+            | (
+                ExpressionKind::Primary(Primary::Literal(Literal::Null)),
+                ExpressionKind::Primary(Primary::Identifier(identifier)),
+            ) => identifier,
+            _ => return None,
+        };
+
+        let variable = self.get_variable(identifier)?;
+        match (&variable.typ, &variable.location) {
+            (VariableType::Nullable(_), VariableLocation::Stack(loc)) => Some((*loc, is_null)),
+            _ => None,
+        }
+    }
+
+    /// Errors if the pointer may be null.
+    pub fn check_not_nullable(pos: Range<usize>, typ: &VariableType) -> Result<(), CompilerError> {
+        match typ {
+            VariableType::Nullable(_) => Err(CompilerError::new(
+                pos,
+                CompilerErrorKind::NullableDereference(typ.clone()),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pushes the pointer about to be dereferenced.
+    /// In checked mode the program exits if it is null.
+    pub fn handle_dereferenced_pointer(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<Builder, CompilerError> {
+        let builder = self.handle_expression(expression)?;
+        Ok(self.with_null_check(builder, &expression.pos))
+    }
+
+    /// Adds a null check of the pointer pushed by the builder in checked mode.
+    pub fn with_null_check(&self, builder: Builder, pos: &Range<usize>) -> Builder {
+        if self.checked {
+            builder.push(Procedure::new(pos.clone(), ProcedureKind::NullCheck))
+        } else {
+            builder
+        }
+    }
+}
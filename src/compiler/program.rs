@@ -2,17 +2,20 @@ use std::collections::HashMap;
 
 use super::{
     builder::Builder,
-    definition::{Function, GlobalData, Procedure, ProcedureCall, ProcedureKind, Struct},
+    definition::{
+        ExternFunction, Function, GenericFunction, GlobalData, Instance, OperandValue, Procedure,
+        ProcedureCall, ProcedureKind, Struct, Trait, TypeAlias,
+    },
     error::{CompilerError, CompilerErrorKind},
     scope::{
-        function_scope::FunctionScope,
-        root_scope::RootScope,
-        variable::{VariableLocation, VariableType},
-        ProgramScope,
+        function_scope::FunctionScope, root_scope::RootScope, variable::VariableType, ProgramScope,
     },
 };
 
-use crate::{lexer::Keyword, parser::definition::Declaration};
+use crate::{
+    lexer::Keyword,
+    parser::definition::{Declaration, DeclarationKind},
+};
 
 #[derive(Debug)]
 pub struct Program {
@@ -20,6 +23,17 @@ pub struct Program {
     pub global_data: HashMap<String, GlobalData>,
     pub functions: Vec<Function>,
     pub structs: Vec<Struct>,
+    pub type_aliases: HashMap<String, TypeAlias>,
+    pub traits: HashMap<String, Trait>,
+    pub generic_functions: HashMap<String, GenericFunction>,
+    pub extern_functions: HashMap<String, ExternFunction>,
+
+    // Instances of generic functions which have been called, but not yet compiled
+    pub instances: Vec<Instance>,
+
+    // Types of `Self` and the type parameters of the function being compiled
+    pub type_parameters: HashMap<String, VariableType>,
+
     pub procedures: Builder,
     pub stack_pos: usize,
 
@@ -29,6 +43,12 @@ pub struct Program {
     // Keep track of how many literal
     // values there are in global data section.
     pub literal_index: usize,
+
+    // Check indices against the length of arrays and slices at runtime
+    pub checked: bool,
+
+    // Compile without a main function, to be linked into other programs
+    pub library: bool,
 }
 
 impl Program {
@@ -38,13 +58,31 @@ impl Program {
             global_data: HashMap::new(),
             functions: Vec::new(),
             structs: Vec::new(),
+            type_aliases: HashMap::new(),
+            traits: HashMap::new(),
+            generic_functions: HashMap::new(),
+            extern_functions: HashMap::new(),
+            instances: Vec::new(),
+            type_parameters: HashMap::new(),
             procedures: Builder::new(),
             stack_pos: 0,
             literal_index: 0,
             main_func_nparams: 0,
+            checked: false,
+            library: false,
         }
     }
 
+    pub fn with_checks(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    pub fn with_library(mut self, library: bool) -> Self {
+        self.library = library;
+        self
+    }
+
     /// Used when entering a new function scope.
     /// Makes sure that variables are properly scoped.
     // todo: clean up
@@ -56,7 +94,8 @@ impl Program {
     where
         F: FnOnce(&mut Self) -> Result<Builder, CompilerError>,
     {
-        let mut function_scope = ProgramScope::FunctionScope(FunctionScope::new(return_type));
+        let mut function_scope =
+            ProgramScope::FunctionScope(Box::new(FunctionScope::new(return_type)));
         std::mem::swap(&mut function_scope, &mut self.scope);
 
         let ProgramScope::FunctionScope(own_function_scope) = &mut self.scope else {
@@ -92,13 +131,14 @@ impl Program {
             }
         }
 
-        let result = inner(self)?;
+        let mut result = inner(self)?;
 
         match &mut self.scope {
             ProgramScope::RootScope(scope) => {
                 scope.close_scope();
             }
             ProgramScope::FunctionScope(scope) => {
+                result = result.append(scope.scope.get_deferred());
                 scope.close_scope();
             }
         }
@@ -106,9 +146,58 @@ impl Program {
         Ok(result)
     }
 
+    /// Used when compiling methods and instances of generic functions.
+    /// Makes sure that `Self` and type parameters resolve to the given types.
+    pub fn with_type_parameters<F, T>(
+        &mut self,
+        type_parameters: HashMap<String, VariableType>,
+        inner: F,
+    ) -> Result<T, CompilerError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, CompilerError>,
+    {
+        let outer = std::mem::replace(&mut self.type_parameters, type_parameters);
+        let result = inner(self);
+        self.type_parameters = outer;
+
+        result
+    }
+
     pub fn compile(mut self, parsed: Vec<Declaration>) -> Result<Program, CompilerError> {
+        // Register type aliases and traits up front, so that they
+        // can be used before they are declared.
+        for declaration in &parsed {
+            match &declaration.kind {
+                DeclarationKind::TypeAlias(alias) => self.register_type_alias(alias)?,
+                DeclarationKind::TraitDeclaration(tdec) => self.register_trait(tdec)?,
+                _ => (),
+            }
+        }
+
+        // Generic functions are compiled for every type they are called with,
+        // after the rest of the program.
+        let (generic_functions, parsed): (Vec<_>, Vec<_>) =
+            parsed
+                .into_iter()
+                .partition(|declaration| match &declaration.kind {
+                    DeclarationKind::FunctionDeclaration(fdec) => !fdec.generics.is_empty(),
+                    _ => false,
+                });
+
+        for declaration in generic_functions {
+            self.register_generic_function(declaration)?;
+        }
+
         // Compile main program
         let procedures = self.get_procedures(&parsed)?;
+        self.compile_instances()?;
+
+        // Libraries are not run on their own,
+        // the code outside of functions initializes their globals when they are loaded.
+        if self.library {
+            self.procedures = procedures;
+            return Ok(self);
+        }
 
         // Get root scope in order to find main function
         let ProgramScope::RootScope(root_scope) = &self.scope else {
@@ -116,7 +205,6 @@ impl Program {
         };
 
         // Find main function in root scope
-        // Todo: maybe main function shouldn't be required?
         let Some(&VariableType::Function(main_func_id)) = root_scope.scope.variables.get("main").map(|var| &var.typ) else {
             return Err(CompilerError::new(parsed.last().map(|v| v.pos.clone()).unwrap_or(0..0), CompilerErrorKind::MissingMainFunction))
         };
@@ -140,7 +228,7 @@ impl Program {
         let params = &main_func
             .parameters
             .iter()
-            .map(|v| (self.get_variable_type(&v.typ), v.pos.clone()))
+            .map(|v| (v.typ.clone(), v.pos.clone()))
             .collect::<Vec<_>>()[..];
 
         self.main_func_nparams = match params {
@@ -157,6 +245,7 @@ impl Program {
                     CompilerErrorKind::WrongType {
                         got: first.clone(),
                         expected: first_type,
+                        expected_alias: None,
                     },
                 ))
             }
@@ -167,38 +256,44 @@ impl Program {
                     CompilerErrorKind::WrongType {
                         got: second.clone(),
                         expected: second_type,
+                        expected_alias: None,
                     },
                 ))
             }
             [_, _] => 2,
         };
 
+        // The value returned by the main function is the exit status,
+        // which is 0 if it does not return anything.
+        let return_slots = match &main_func.return_type {
+            None => 0,
+            Some(VariableType::Value(Keyword::Int)) => 1,
+            Some(typ) => {
+                return Err(CompilerError::new(
+                    main_func.identifier_pos.clone(),
+                    CompilerErrorKind::WrongMainReturnType(typ.clone()),
+                ))
+            }
+        };
+
         // Create the call to the main function
         let call_main_func = Procedure::new(
             main_func.identifier_pos.clone(),
             ProcedureKind::ProcedureCall(ProcedureCall {
                 function_id: main_func_id,
-                nargs: self.main_func_nparams,
-                returns: false,
+                args: vec![1; self.main_func_nparams],
+                return_slots,
             }),
         );
 
-        // Add global variables to bss section
-        for variable in root_scope.variables.values() {
-            if let VariableType::Value(_) = variable.typ {
-                #[allow(clippy::single_match)]
-                match &variable.location {
-                    VariableLocation::Global(label) => {
-                        // Todo: currently only integers
-                        self.global_data
-                            .insert(label.clone(), GlobalData::Reserved(1));
-                    }
-                    _ => {}
-                };
-            }
-        }
+        let mut builder = Builder::new().append(procedures).push(call_main_func);
 
-        let builder = Builder::new().append(procedures).push(call_main_func);
+        if return_slots == 0 {
+            builder = builder.push(Procedure::new(
+                main_func.identifier_pos.clone(),
+                ProcedureKind::Push(OperandValue::Int(0)),
+            ));
+        }
 
         self.procedures = builder;
 
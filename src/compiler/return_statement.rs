@@ -2,10 +2,13 @@ use crate::parser::definition::{ReturnStatement, Statement};
 
 use super::{
     builder::Builder,
-    definition::{Procedure, ProcedureKind},
-    error::CompilerError,
+    definition::{Assign, OperandValue, Procedure, ProcedureKind},
+    error::{CompilerError, CompilerErrorKind},
     program::Program,
-    scope::ProgramScope,
+    scope::{
+        variable::{Variable, VariableLocation},
+        ProgramScope,
+    },
 };
 
 impl Program {
@@ -18,19 +21,120 @@ impl Program {
             todo!("We're not in a function");
         };
 
-        let Some(return_type) = &function_scope.return_type else {
-            todo!("Cannot return a value from this function");
-        };
+        let return_pointer = function_scope.return_pointer;
+        let return_type_pos = function_scope.return_type_pos.clone().unwrap_or_default();
+
+        // The deferred code of every enclosing scope runs after the value has been evaluated
+        let deferred = function_scope.scope.get_enclosing_deferred();
 
-        let typ = self.infer_type(&ret_statement.value)?;
+        let (typ, value) = match (function_scope.return_type.clone(), &ret_statement.value) {
+            (Some(typ), Some(value)) => (typ, value),
+            (None, None) => {
+                self.mark_unreachable();
 
-        if *return_type != typ {
-            todo!("Wrong return type")
+                return Ok(deferred.push(Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Return(0),
+                )));
+            }
+            (None, Some(value)) => {
+                return Err(CompilerError::new(
+                    value.pos.clone(),
+                    CompilerErrorKind::UnexpectedReturnValue,
+                ))
+            }
+            (Some(expected), None) => {
+                return Err(CompilerError::new(
+                    statement.pos.clone(),
+                    CompilerErrorKind::MissingReturnValue {
+                        expected,
+                        return_type_pos,
+                    },
+                ))
+            }
+        };
+
+        let got = self.infer_type(value)?;
+        if !self.is_coercible(&got, &typ) {
+            return Err(CompilerError::new(
+                value.pos.clone(),
+                CompilerErrorKind::WrongReturnType {
+                    got,
+                    expected: typ,
+                    return_type_pos,
+                },
+            ));
         }
 
-        let builder = Builder::new()
-            .append(self.handle_expression(&ret_statement.value)?)
-            .push(Procedure::new(statement.pos.clone(), ProcedureKind::Return));
+        let slots = Self::get_type_slots(&typ);
+
+        // Large values are written to memory reserved by the caller,
+        // and the pointer to it is returned.
+        let builder = if let Some(return_pointer) = return_pointer {
+            let pointer = || {
+                Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Push(OperandValue::StackLocation(return_pointer)),
+                )
+            };
+
+            Builder::new()
+                .push(pointer())
+                .append(self.handle_coerced_expression(value, &typ)?)
+                .push(Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Assign(Assign {
+                        location: VariableLocation::Address,
+                        size: Self::get_type_size(&typ),
+                    }),
+                ))
+                .append(deferred)
+                .push(pointer())
+                .push(Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Return(1),
+                ))
+        } else if deferred.procedures.is_empty() {
+            Builder::new()
+                .append(self.handle_coerced_expression(value, &typ)?)
+                .push(Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Return(slots),
+                ))
+        } else {
+            // The value is kept in a hidden variable while the deferred code runs
+            let location = self.create_variable(
+                ".return".to_string(),
+                Variable {
+                    pos: statement.pos.clone(),
+                    typ: typ.clone(),
+                    mutable: true,
+                },
+            );
+
+            let builder = Builder::new()
+                .append(self.handle_coerced_expression(value, &typ)?)
+                .push(Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Assign(Assign {
+                        location: location.clone(),
+                        size: Self::get_type_size(&typ),
+                    }),
+                ))
+                .append(deferred);
+
+            Self::get_push_procedures(&typ, &location, false)
+                .into_iter()
+                .fold(builder, |builder, kind| {
+                    builder.push(Procedure::new(statement.pos.clone(), kind))
+                })
+                .push(Procedure::new(
+                    statement.pos.clone(),
+                    ProcedureKind::Return(slots),
+                ))
+        };
+
+        self.mark_unreachable();
 
         Ok(builder)
     }
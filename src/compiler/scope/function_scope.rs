@@ -1,4 +1,8 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    rc::Rc,
+};
 
 use super::{
     variable::{StoredVariable, Variable, VariableLocation, VariableType},
@@ -11,12 +15,33 @@ pub struct FunctionScope {
     pub scope: Scope,
     pub variables: HashMap<String, Rc<StoredVariable>>,
     pub return_type: Option<VariableType>,
+    pub return_type_pos: Option<Range<usize>>,
+
+    // Stack position of the hidden parameter pointing to
+    // the memory large return values are written to.
+    pub return_pointer: Option<isize>,
 
     // Keep track of assigned variables.
     // Will grow by +1 for each declared variable.
     stack_pos: usize,
 
-    // Keep track of assigned parameters
+    // Stack positions of variables which have been
+    // declared without a value, and which might
+    // not have been assigned at this point in the code.
+    pub unassigned: HashSet<isize>,
+
+    // Stack positions of variables which might
+    // have been assigned at this point in the code.
+    pub assigned: HashSet<isize>,
+
+    // Stack positions of `pointer?<T>` variables
+    // which are known to not be null at this point in the code.
+    pub non_null: HashSet<isize>,
+
+    // Stack positions of the parameters, which cannot be shadowed.
+    parameters: HashSet<isize>,
+
+    // Keep track of parameters passed on the stack.
     // The value will grow negatively,
     // since parameters reside below `rbp`.
     par_pos: isize,
@@ -29,7 +54,15 @@ impl FunctionScope {
             scope: Scope::new(),
             variables: HashMap::new(),
             return_type,
-            stack_pos: 1,
+            return_type_pos: None,
+            return_pointer: None,
+
+            // The first slot below `rbp` holds the saved `rbx`.
+            stack_pos: 2,
+            unassigned: HashSet::new(),
+            assigned: HashSet::new(),
+            non_null: HashSet::new(),
+            parameters: HashSet::new(),
 
             // First two elements are
             // old rbp and return address,
@@ -58,24 +91,27 @@ impl FunctionScope {
         self.scope = self.scope.take_parent();
     }
 
-    fn create_stored_variable(&mut self, variable: Variable) -> StoredVariable {
+    /// `slots` is the amount of 8 byte stack slots the variable needs.
+    fn create_stored_variable(&mut self, variable: Variable, slots: usize) -> StoredVariable {
         let stored = StoredVariable {
             variable,
             location: VariableLocation::Stack(self.stack_pos as isize),
         };
 
-        self.stack_pos += 1;
+        self.stack_pos += slots;
 
         stored
     }
 
-    fn create_stored_parameter(&mut self, variable: Variable) -> StoredVariable {
+    /// The caller places parameters on the stack in memory order,
+    /// so those of several slots start at the highest address.
+    fn create_stored_parameter(&mut self, variable: Variable, slots: usize) -> StoredVariable {
         let stored = StoredVariable {
             variable,
-            location: VariableLocation::Stack(self.par_pos),
+            location: VariableLocation::Stack(self.par_pos - slots as isize + 1),
         };
 
-        self.par_pos -= 1;
+        self.par_pos -= slots as isize;
 
         stored
     }
@@ -83,12 +119,17 @@ impl FunctionScope {
     /// Creates a stack allocated variable in the current function scope.
     /// Also adds it to the current scope variable lookup.
     /// Returns the location of the variable.
-    pub fn create_variable(&mut self, identifier: String, variable: Variable) -> VariableLocation {
-        let stored = Rc::new(self.create_stored_variable(variable));
+    pub fn create_variable(
+        &mut self,
+        identifier: String,
+        variable: Variable,
+        slots: usize,
+    ) -> VariableLocation {
+        let stored = Rc::new(self.create_stored_variable(variable, slots));
         let location = stored.location.clone();
 
         self.variables.insert(
-            self.scope.get_scoped_variable_name(&identifier),
+            self.scope.create_scoped_variable_name(&identifier),
             stored.clone(),
         );
         self.scope.insert_variable(identifier, stored);
@@ -96,13 +137,28 @@ impl FunctionScope {
         location
     }
 
-    /// Creates a stack allocated parameter similarly to `create_variable`.
-    pub fn create_parameter(&mut self, identifier: String, variable: Variable) -> VariableLocation {
-        let stored = Rc::new(self.create_stored_parameter(variable));
+    /// Creates a parameter similarly to `create_variable`.
+    /// Parameters passed in registers are stored with the variables,
+    /// those passed on the stack are read where the caller placed them.
+    pub fn create_parameter(
+        &mut self,
+        identifier: String,
+        variable: Variable,
+        slots: usize,
+        on_stack: bool,
+    ) -> VariableLocation {
+        let stored = Rc::new(match on_stack {
+            true => self.create_stored_parameter(variable, slots),
+            false => self.create_stored_variable(variable, slots),
+        });
         let location = stored.location.clone();
 
+        if let VariableLocation::Stack(loc) = location {
+            self.parameters.insert(loc);
+        }
+
         self.variables.insert(
-            self.scope.get_scoped_variable_name(&identifier),
+            self.scope.create_scoped_variable_name(&identifier),
             stored.clone(),
         );
         self.scope.insert_variable(identifier, stored);
@@ -110,6 +166,10 @@ impl FunctionScope {
         location
     }
 
+    pub fn is_parameter(&self, location: &VariableLocation) -> bool {
+        matches!(location, VariableLocation::Stack(loc) if self.parameters.contains(loc))
+    }
+
     pub fn get_variable(&self, identifier: &String) -> Option<&StoredVariable> {
         self.scope.get_variable(identifier).or_else(|| {
             self.parent
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, rc::Rc};
 
+use super::builder::Builder;
+
 use self::{function_scope::FunctionScope, root_scope::RootScope, variable::StoredVariable};
 
 pub mod function_scope;
@@ -8,7 +10,7 @@ pub mod variable;
 
 #[derive(Debug)]
 pub enum ProgramScope {
-    FunctionScope(FunctionScope),
+    FunctionScope(Box<FunctionScope>),
     RootScope(RootScope),
 }
 
@@ -18,6 +20,14 @@ pub struct Scope {
     pub variables: HashMap<String, Rc<StoredVariable>>,
     pub name: String,      // Prefix for variables
     n_child_scopes: usize, // Amount of child scopes
+
+    // Amount of variables declared with each name,
+    // used to give shadowed variables unique names.
+    n_declarations: HashMap<String, usize>,
+
+    // Code of the `defer` statements in this scope,
+    // in the order they were declared.
+    pub deferred: Vec<Builder>,
 }
 
 impl Scope {
@@ -27,6 +37,8 @@ impl Scope {
             variables: HashMap::new(),
             name: String::new(),
             n_child_scopes: 0,
+            n_declarations: HashMap::new(),
+            deferred: Vec::new(),
         }
     }
 
@@ -34,8 +46,16 @@ impl Scope {
         format!("{}_{}", self.name, self.n_child_scopes)
     }
 
-    pub fn get_scoped_variable_name(&self, name: &str) -> String {
-        format!("{}__{}", self.name, name)
+    /// Returns a unique name for a new variable declared in this scope.
+    /// Variables shadowing another one in the same scope get a numbered suffix.
+    pub fn create_scoped_variable_name(&mut self, name: &str) -> String {
+        let n = self.n_declarations.entry(name.to_string()).or_insert(0);
+        *n += 1;
+
+        match *n {
+            1 => format!("{}__{}", self.name, name),
+            n => format!("{}__{}.{}", self.name, name, n - 1),
+        }
     }
 
     pub fn set_parent(&mut self, mut parent: Box<Scope>) {
@@ -58,33 +78,33 @@ impl Scope {
         }
     }
 
-    /// Returns the identifier and variable if it was not set
-    fn insert_variable_inner(
-        &mut self,
-        identifier: String,
-        variable: Rc<StoredVariable>,
-    ) -> Option<(String, Rc<StoredVariable>)> {
-        if self.variables.get(&identifier).is_some() {
-            self.variables.insert(identifier, variable);
-            None
-        } else if let Some(ref mut parent) = self.parent {
-            parent.insert_variable_inner(identifier, variable);
-            None
-        } else {
-            Some((identifier, variable))
+    /// Variables declared in this scope, not including parent scopes.
+    pub fn get_local_variable(&self, identifier: &String) -> Option<&StoredVariable> {
+        self.variables
+            .get(identifier)
+            .map(|variable| variable.as_ref())
+    }
+
+    /// The deferred code run when this scope is exited,
+    /// the most recently deferred first.
+    pub fn get_deferred(&self) -> Builder {
+        self.deferred
+            .iter()
+            .rev()
+            .cloned()
+            .fold(Builder::new(), Builder::append)
+    }
+
+    /// The deferred code of this scope and all its parents, from the innermost scope outwards.
+    pub fn get_enclosing_deferred(&self) -> Builder {
+        match &self.parent {
+            Some(parent) => self.get_deferred().append(parent.get_enclosing_deferred()),
+            None => self.get_deferred(),
         }
     }
 
+    /// New variables shadow earlier ones with the same name.
     pub fn insert_variable(&mut self, identifier: String, variable: Rc<StoredVariable>) {
-        if self.variables.get(&identifier).is_some() {
-            self.variables.insert(identifier, variable);
-        } else if let Some(ref mut parent) = self.parent {
-            if let Some((identifier, variable)) = parent.insert_variable_inner(identifier, variable)
-            {
-                self.variables.insert(identifier, variable);
-            }
-        } else {
-            self.variables.insert(identifier, variable);
-        }
+        self.variables.insert(identifier, variable);
     }
 }
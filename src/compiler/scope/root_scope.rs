@@ -34,19 +34,14 @@ impl RootScope {
     /// Creates a global allocated variable in the root scope.
     /// Returns the label name of the variable.
     pub fn create_variable(&mut self, identifier: String, variable: Variable) -> VariableLocation {
-        let location = VariableLocation::Global(format!(
-            "_global_{}",
-            self.scope.get_scoped_variable_name(&identifier)
-        ));
+        let name = self.scope.create_scoped_variable_name(&identifier);
+        let location = VariableLocation::Global(format!("_global_{name}"));
         let stored = Rc::new(StoredVariable {
             variable,
             location: location.clone(),
         });
 
-        self.variables.insert(
-            self.scope.get_scoped_variable_name(&identifier),
-            stored.clone(),
-        );
+        self.variables.insert(name, stored.clone());
 
         self.scope.insert_variable(identifier, stored);
 
@@ -1,6 +1,7 @@
 use std::{
     fmt::Display,
     ops::{Deref, Range},
+    rc::Rc,
 };
 
 use crate::lexer::Keyword;
@@ -12,21 +13,64 @@ use crate::lexer::Keyword;
 pub struct Variable {
     pub pos: Range<usize>,
     pub typ: VariableType,
+    pub mutable: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct StructType {
     pub id: usize,
     pub identifier: String,
     pub size: usize, // size in bytes
+    pub alignment: usize,
+
+    // Set inside of a generic function, when the struct is the type argument of a type parameter
+    pub type_parameter: Option<Rc<TypeParameter>>,
+}
+
+// Only the struct decides the type, not the type parameter it was passed as
+impl PartialEq for StructType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// `T` in `fn name<T: Show>()`, only the methods of its bounds can be called on it.
+#[derive(Debug)]
+pub struct TypeParameter {
+    pub identifier: String,
+    pub pos: Range<usize>,
+    pub bounds: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum VariableType {
     Value(Keyword),
     Pointer(Box<VariableType>),
+
+    // Pointer which may be null, it has to be compared to null before it is dereferenced
+    Nullable(Box<VariableType>),
+
+    // Type of the `null` literal, which can be stored in any pointer
+    Null,
+
+    // Element type and length
+    Array(Box<VariableType>, usize),
+
+    // Element type, stored as a pointer to the first element followed by the length
+    Slice(Box<VariableType>),
+
+    // Only used as the type pointed to by a `pointer<const T>`
+    Const(Box<VariableType>),
+
     Function(usize), // function id
+    FunctionPointer(Vec<VariableType>, Option<Box<VariableType>>),
     Struct(StructType),
+
+    // Laid out like a struct with the fields `0`, `1`, ...
+    Tuple(Vec<VariableType>),
+
+    // Trait identifier, stored as a pointer to the value followed by a pointer to the vtable
+    Dyn(String),
 }
 
 #[derive(Debug, Clone)]
@@ -58,6 +102,16 @@ impl Deref for StoredVariable {
     }
 }
 
+impl VariableType {
+    /// The type of the value itself, ignoring if it's `const`.
+    pub fn without_const(self) -> VariableType {
+        match self {
+            VariableType::Const(typ) => *typ,
+            typ => typ,
+        }
+    }
+}
+
 impl Display for VariableType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -65,6 +119,17 @@ impl Display for VariableType {
                 write!(f, "*")?;
                 pointer.fmt(f)
             }
+            VariableType::Nullable(pointer) => {
+                write!(f, "?*")?;
+                pointer.fmt(f)
+            }
+            VariableType::Null => write!(f, "null"),
+            VariableType::Array(typ, length) => write!(f, "[{typ}; {length}]"),
+            VariableType::Slice(typ) => write!(f, "&[{typ}]"),
+            VariableType::Const(typ) => {
+                write!(f, "const ")?;
+                typ.fmt(f)
+            }
             VariableType::Value(value) => {
                 let v = match value {
                     Keyword::Int => "int",
@@ -74,6 +139,43 @@ impl Display for VariableType {
 
                 write!(f, "{v}")
             }
+            VariableType::Tuple(types) => {
+                write!(f, "(")?;
+                for (i, typ) in types.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    typ.fmt(f)?;
+                }
+
+                // A single element tuple keeps its comma, `(int,)`
+                if types.len() == 1 {
+                    write!(f, ",")?;
+                }
+
+                write!(f, ")")
+            }
+            VariableType::Struct(struct_type) => write!(f, "{}", struct_type.identifier),
+            VariableType::Dyn(trait_identifier) => write!(f, "&dyn {trait_identifier}"),
+            VariableType::FunctionPointer(parameters, return_type) => {
+                write!(f, "fn(")?;
+                for (i, parameter) in parameters.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    parameter.fmt(f)?;
+                }
+                write!(f, ")")?;
+
+                if let Some(return_type) = return_type {
+                    write!(f, " -> ")?;
+                    return_type.fmt(f)?;
+                }
+
+                Ok(())
+            }
             _ => write!(f, "{self:?}"),
         }
     }
@@ -0,0 +1,190 @@
+use crate::{
+    lexer::Keyword,
+    parser::definition::{ArraySlice, Expression, MemberAccess},
+};
+
+use super::{
+    builder::Builder,
+    definition::{Arithmetic, Assign, OperandValue, Procedure, ProcedureKind, RegisterSize},
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::VariableType,
+};
+
+/// Slices take up two slots, the pointer to the first element
+/// is stored at the address of the slice and the length right after it.
+impl Program {
+    pub fn infer_slice_type(
+        &self,
+        expression: &Expression,
+        slice: &ArraySlice,
+    ) -> Result<VariableType, CompilerError> {
+        for bound in slice.start.iter().chain(slice.end.iter()) {
+            let typ = self.infer_type(bound)?;
+            if !matches!(typ, VariableType::Value(Keyword::Int)) {
+                return Err(CompilerError::new(
+                    bound.pos.clone(),
+                    CompilerErrorKind::NonIntegerSliceBound(typ),
+                ));
+            }
+        }
+
+        match self.infer_type(&slice.left)? {
+            VariableType::Array(element_type, _) | VariableType::Slice(element_type) => {
+                Ok(VariableType::Slice(element_type))
+            }
+            // The length of the memory behind a pointer is not known
+            typ @ VariableType::Pointer(_) if slice.end.is_none() => Err(CompilerError::new(
+                expression.pos.clone(),
+                CompilerErrorKind::UnboundedSlice(typ),
+            )),
+            VariableType::Pointer(element_type) => Ok(VariableType::Slice(element_type)),
+            typ => Err(CompilerError::new(
+                slice.left.pos.clone(),
+                CompilerErrorKind::DereferenceNonPointer(typ),
+            )),
+        }
+    }
+
+    /// Slices only have a single field, `len`.
+    pub fn check_slice_member(
+        &self,
+        expression: &Expression,
+        access: &MemberAccess,
+    ) -> Result<(), CompilerError> {
+        if access.member == "len" {
+            return Ok(());
+        }
+
+        Err(CompilerError::new(
+            expression.pos.clone(),
+            CompilerErrorKind::UndefinedSliceField(access.member.clone()),
+        ))
+    }
+
+    /// Pushes the pointer to the first element of the slice.
+    pub fn handle_slice_pointer(&mut self, slice: &Expression) -> Result<Builder, CompilerError> {
+        Ok(self
+            .handle_ref(slice)?
+            .push(Procedure::new(slice.pos.clone(), ProcedureKind::Deref)))
+    }
+
+    /// Pushes the length of the slice.
+    pub fn handle_slice_length(&mut self, slice: &Expression) -> Result<Builder, CompilerError> {
+        Ok(self
+            .handle_ref(slice)?
+            .push(Procedure::new(
+                slice.pos.clone(),
+                ProcedureKind::Push(OperandValue::Int(8)),
+            ))
+            .push(Procedure::new(
+                slice.pos.clone(),
+                ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+            ))
+            .push(Procedure::new(slice.pos.clone(), ProcedureKind::Deref)))
+    }
+
+    /// Pushes the pointer and then the length of the new slice.
+    /// A sliced slice and the start index are needed for both of them,
+    /// so they are evaluated once into temporaries.
+    pub fn handle_array_slice(
+        &mut self,
+        expression: &Expression,
+        slice: &ArraySlice,
+    ) -> Result<Builder, CompilerError> {
+        let VariableType::Slice(element_type) = self.infer_slice_type(expression, slice)? else {
+            unreachable!()
+        };
+
+        let pos = &expression.pos;
+        let push =
+            |builder: Builder, kind: ProcedureKind| builder.push(Procedure::new(pos.clone(), kind));
+
+        let left_type = self.infer_type(&slice.left)?;
+        let mut builder = Builder::new();
+
+        let stored_slice = match left_type {
+            VariableType::Slice(_) => {
+                let location = self.create_temporary(".sliced", &left_type, slice.left.pos.clone());
+                builder = push(
+                    self.handle_expression(&slice.left)?,
+                    ProcedureKind::Assign(Assign {
+                        location: location.clone(),
+                        size: Self::get_type_size(&left_type),
+                    }),
+                );
+                Some(location)
+            }
+            _ => None,
+        };
+
+        let index_type = VariableType::Value(Keyword::Int);
+        let stored_start = match &slice.start {
+            Some(start) => {
+                let location = self.create_temporary(".start", &index_type, start.pos.clone());
+                builder = push(
+                    builder.append(self.handle_expression(start)?),
+                    ProcedureKind::Assign(Assign {
+                        location: location.clone(),
+                        size: Self::get_type_size(&index_type),
+                    }),
+                );
+                Some(location)
+            }
+            None => None,
+        };
+
+        builder = match (&left_type, &stored_slice) {
+            // Arrays are sliced from the address of their first element
+            (VariableType::Array(..), _) => builder.append(self.handle_ref(&slice.left)?),
+            (_, Some(location)) => Self::get_push_procedures(&left_type, location, true)
+                .into_iter()
+                .chain([ProcedureKind::Deref])
+                .fold(builder, push),
+            _ => builder.append(self.handle_expression(&slice.left)?),
+        };
+
+        if let Some(location) = &stored_start {
+            builder = Self::get_push_procedures(&index_type, location, false)
+                .into_iter()
+                .chain([
+                    ProcedureKind::Push(OperandValue::Int(
+                        Self::get_type_size(&element_type) as i32
+                    )),
+                    ProcedureKind::Arithmetic(Arithmetic::Multiply, RegisterSize::B64),
+                    ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+                ])
+                .fold(builder, push);
+        }
+
+        let end = match (&slice.end, &left_type, &stored_slice) {
+            (Some(end), ..) => self.handle_expression(end)?,
+            (None, VariableType::Array(_, length), _) => push(
+                Builder::new(),
+                ProcedureKind::Push(OperandValue::Int(*length as i32)),
+            ),
+            (None, _, Some(location)) => Self::get_push_procedures(&left_type, location, true)
+                .into_iter()
+                .chain([
+                    ProcedureKind::Push(OperandValue::Int(8)),
+                    ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+                    ProcedureKind::Deref,
+                ])
+                .fold(Builder::new(), push),
+            (None, ..) => unreachable!("Pointers are only sliced with an end index"),
+        };
+
+        let Some(location) = &stored_start else {
+            return Ok(builder.append(end));
+        };
+
+        Ok(Self::get_push_procedures(&index_type, location, false)
+            .into_iter()
+            .fold(builder, push)
+            .append(end)
+            .push(Procedure::new(
+                pos.clone(),
+                ProcedureKind::Arithmetic(Arithmetic::Subtract, RegisterSize::B64),
+            )))
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    lexer::{Keyword, Literal},
+    parser::definition::{ArrayLiteral, Expression, ExpressionKind, Primary, Unary},
+};
+
+use super::{
+    definition::OperandValue,
+    error::CompilerError,
+    program::Program,
+    scope::variable::{VariableLocation, VariableType},
+    struct_construction::FieldValue,
+};
+
+impl Program {
+    /// Whether the expression can be evaluated at compile time,
+    /// and thereby be placed directly in the data section.
+    pub fn is_static_expression(&self, expression: &Expression) -> bool {
+        match &expression.kind {
+            ExpressionKind::Primary(Primary::Literal(_)) | ExpressionKind::TypeInfo(_) => true,
+            ExpressionKind::Primary(Primary::Identifier(identifier)) => matches!(
+                self.get_variable(identifier).map(|variable| &variable.typ),
+                Some(VariableType::Function(_))
+            ),
+            // Elements sharing a slot are packed at runtime
+            ExpressionKind::ArrayLiteral(array)
+                if self.infer_array_literal_type(expression, array).is_ok_and(
+                    |typ| match typ {
+                        VariableType::Array(element_type, _) => {
+                            Self::get_type_size(&element_type) % 8 != 0
+                        }
+                        _ => false,
+                    },
+                ) =>
+            {
+                false
+            }
+            ExpressionKind::ArrayLiteral(ArrayLiteral::List(elements)) => elements
+                .iter()
+                .all(|element| self.is_static_expression(element)),
+            ExpressionKind::ArrayLiteral(ArrayLiteral::Repeat(value, _)) => {
+                self.is_static_expression(value)
+            }
+            ExpressionKind::Cast(cast) => self.is_static_expression(&cast.expr),
+            // Fields sharing a slot are packed at runtime
+            ExpressionKind::StructConstruction(sconst) => {
+                sconst.base.is_none()
+                    && self.is_unpacked_struct(sconst)
+                    && sconst
+                        .fields
+                        .iter()
+                        .all(|(_, field)| self.is_static_expression(&field.expr))
+            }
+            // The address of a global is known at link time
+            ExpressionKind::Unary(Unary {
+                expr,
+                operator: Keyword::Ampersand,
+                ..
+            }) => match &expr.kind {
+                ExpressionKind::Primary(Primary::Identifier(identifier)) => matches!(
+                    self.get_variable(identifier)
+                        .map(|variable| &variable.location),
+                    Some(VariableLocation::Global(_))
+                ),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the values of a static expression, one per 8 byte slot,
+    /// in the same order as they would have been pushed to the stack.
+    pub fn get_static_data(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<Vec<OperandValue>, CompilerError> {
+        match &expression.kind {
+            ExpressionKind::Primary(Primary::Literal(literal)) => Ok(vec![match literal {
+                Literal::Int(i) => OperandValue::Int(*i),
+                Literal::Bool(b) => OperandValue::Int(if *b { 1 } else { 0 }),
+                Literal::Null => OperandValue::Int(0),
+                Literal::String(s) => OperandValue::DataPointerLocation(self.add_string_literal(s)),
+            }]),
+            ExpressionKind::Primary(Primary::Identifier(identifier)) => {
+                let Some(VariableType::Function(function_id)) =
+                    self.get_variable(identifier).map(|variable| &variable.typ)
+                else {
+                    unreachable!("Expression is not static");
+                };
+
+                Ok(vec![OperandValue::FunctionLocation(*function_id)])
+            }
+            ExpressionKind::TypeInfo(info) => Ok(vec![OperandValue::Int(
+                self.get_type_info_value(info)? as i32,
+            )]),
+            ExpressionKind::ArrayLiteral(array) => {
+                // Type checks the elements
+                self.infer_array_literal_type(expression, array)?;
+
+                let mut data = Vec::new();
+
+                for element in Self::get_array_literal_elements(array) {
+                    data.append(&mut self.get_static_data(element)?);
+                }
+
+                Ok(data)
+            }
+            ExpressionKind::Cast(cast) => self.get_static_cast(cast),
+            ExpressionKind::StructConstruction(sconst) => {
+                let mut data = Vec::new();
+
+                for field in self.get_struct_construction_fields(sconst)? {
+                    match field.value {
+                        FieldValue::Given(value) => {
+                            data.append(&mut self.get_static_coerced_data(value, &field.typ)?)
+                        }
+                        FieldValue::Default(mut values) => data.append(&mut values),
+                        FieldValue::Base(_) => unreachable!("Expression is not static"),
+                    }
+                }
+
+                Ok(data)
+            }
+            ExpressionKind::Unary(unary) => {
+                let Some(VariableLocation::Global(label)) = unary
+                    .expr
+                    .get_string()
+                    .and_then(|identifier| self.get_variable(&identifier.to_string()))
+                    .map(|variable| &variable.location)
+                else {
+                    unreachable!("Expression is not static");
+                };
+
+                Ok(vec![OperandValue::DataPointerLocation(label.clone())])
+            }
+            _ => unreachable!("Expression is not static"),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::{
     compiler::scope::variable::StructType,
     parser::definition::{Expression, StructConstruction},
@@ -5,19 +7,35 @@ use crate::{
 
 use super::{
     builder::Builder,
+    definition::{Arithmetic, Assign, OperandValue, Procedure, ProcedureKind, RegisterSize},
     error::{CompilerError, CompilerErrorKind},
     program::Program,
-    scope::variable::VariableType,
+    scope::variable::{VariableLocation, VariableType},
 };
 
-impl Program {
-    pub fn handle_struct_construction(
-        &mut self,
-        _expression: &Expression,
-        sconst: &StructConstruction,
-    ) -> Result<Builder, CompilerError> {
-        let mut builder = Builder::new();
+/// Where the value of a field in a struct construction comes from.
+pub enum FieldValue<'b> {
+    Given(&'b Expression),
+    // Read from the same field of `..base`
+    Base(&'b Expression),
+    Default(Vec<OperandValue>),
+}
+
+pub struct ConstructionField<'b> {
+    pub value: FieldValue<'b>,
+    pub typ: VariableType,
+    pub offset: usize,
+    pub size: usize,
+}
 
+impl Program {
+    /// Type checks the fields of the struct construction.
+    /// Returns the fields in the order they should be pushed.
+    /// Fields which are not given are taken from the base, or else their default value.
+    pub fn get_struct_construction_fields<'b>(
+        &self,
+        sconst: &'b StructConstruction,
+    ) -> Result<Vec<ConstructionField<'b>>, CompilerError> {
         let Some(variable) = self.get_variable(&sconst.identifier) else {
             return Err(CompilerError::new(
                 sconst.identifier_pos.clone(),
@@ -25,35 +43,295 @@ impl Program {
             ));
         };
 
-        let VariableType::Struct(StructType {id, size: _size, ..}) = variable.typ else {
+        let VariableType::Struct(StructType { id, .. }) = variable.typ else {
             todo!("Variable is not a struct")
         };
 
         let sdec = self.structs.get(id).unwrap();
+
+        for (i, (field_identifier, field_const)) in sconst.fields.iter().enumerate() {
+            if let Some((_, first)) = sconst.fields[..i]
+                .iter()
+                .find(|(identifier, _)| identifier == field_identifier)
+            {
+                return Err(CompilerError::new(
+                    field_const.pos.clone(),
+                    CompilerErrorKind::DuplicateField {
+                        field: field_identifier.clone(),
+                        first_pos: first.pos.clone(),
+                    },
+                ));
+            }
+
+            if sdec.get_field(field_identifier).is_none() {
+                return Err(CompilerError::new(
+                    field_const.pos.clone(),
+                    CompilerErrorKind::UnknownField {
+                        identifier: sconst.identifier.clone(),
+                        field: field_identifier.clone(),
+                        declaration_pos: sdec.identifier_pos.clone(),
+                    },
+                ));
+            }
+        }
+
+        if let Some(base) = &sconst.base {
+            let base_type = self.infer_type(base)?;
+
+            if !Self::is_assignable(&base_type, &variable.typ) {
+                return Err(CompilerError::new(
+                    base.pos.clone(),
+                    CompilerErrorKind::WrongType {
+                        got: base_type,
+                        expected: variable.typ.clone(),
+                        expected_alias: None,
+                    },
+                ));
+            }
+        }
+
         let mut field_content = Vec::with_capacity(sdec.fields.len());
 
         for (field_identifier, field_dec) in &sdec.fields {
-            let Some(field_const) = sconst.fields.get(field_identifier) else {
-                todo!("Missing field: {}", field_identifier);
+            let field_const = sconst
+                .fields
+                .iter()
+                .find(|(identifier, _)| identifier == field_identifier)
+                .map(|(_, field_const)| field_const);
+
+            let value = match (field_const, &sconst.base, &field_dec.default) {
+                (Some(field_const), ..) => {
+                    let const_typ = self.infer_type(&field_const.expr)?;
+                    if !self.is_coercible(&const_typ, &field_dec.typ) {
+                        return Err(CompilerError::new(
+                            field_const.pos.clone(),
+                            CompilerErrorKind::WrongAssignmentType {
+                                got: const_typ,
+                                typ: field_dec.typ.clone(),
+                                declaration_pos: Some(field_dec.pos.clone()),
+                            },
+                        ));
+                    }
+
+                    FieldValue::Given(&field_const.expr)
+                }
+                (None, Some(base), _) => FieldValue::Base(base),
+                (None, None, Some(default)) => FieldValue::Default(default.clone()),
+                (None, None, None) => {
+                    return Err(CompilerError::new(
+                        sconst.identifier_pos.clone(),
+                        CompilerErrorKind::MissingField {
+                            identifier: sconst.identifier.clone(),
+                            field: field_identifier.clone(),
+                            field_pos: field_dec.pos.clone(),
+                        },
+                    ));
+                }
             };
 
-            let const_typ = self.infer_type(&field_const.expr)?;
-            if field_dec.typ != const_typ {
-                return Err(CompilerError {
-                    pos: field_const.pos.clone(),
-                    kind: CompilerErrorKind::WrongAssignmentType {
-                        got: const_typ,
-                        typ: field_dec.typ.clone(),
-                        declaration_pos: Some(field_dec.pos.clone()),
-                    },
-                });
+            field_content.push(ConstructionField {
+                value,
+                typ: field_dec.typ.clone(),
+                offset: field_dec.offset,
+                size: field_dec.size,
+            });
+        }
+
+        Ok(field_content)
+    }
+
+    /// Whether every field starts a new slot, so that the fields
+    /// can be pushed one after another without being packed together.
+    pub fn is_unpacked_struct(&self, sconst: &StructConstruction) -> bool {
+        let Some(VariableType::Struct(struct_type)) = self
+            .get_variable(&sconst.identifier)
+            .map(|variable| &variable.typ)
+        else {
+            return false;
+        };
+
+        self.structs[struct_type.id]
+            .fields
+            .iter()
+            .all(|(_, field)| field.offset % 8 == 0)
+    }
+
+    /// Stores a pointer to the base in a temporary, so that the base is evaluated once
+    /// before the fields, and the fields taken from it are read in place.
+    fn handle_construction_base(
+        &mut self,
+        base: &Expression,
+    ) -> Result<(Builder, VariableLocation), CompilerError> {
+        self.check_member_access_assigned(base)?;
+
+        let typ = VariableType::Pointer(Box::new(self.infer_type(base)?));
+        let location = self.create_temporary(".base", &typ, base.pos.clone());
+
+        let builder = self.handle_ref(base)?.push(Procedure::new(
+            base.pos.clone(),
+            ProcedureKind::Assign(Assign {
+                location: location.clone(),
+                size: Self::get_type_size(&typ),
+            }),
+        ));
+
+        Ok((builder, location))
+    }
+
+    /// Pushes the value of a single field.
+    /// Fields taken from the base are read through the pointer stored in `base`.
+    fn handle_construction_field(
+        &mut self,
+        expression: &Expression,
+        field: &ConstructionField,
+        base: Option<&VariableLocation>,
+    ) -> Result<Builder, CompilerError> {
+        match &field.value {
+            FieldValue::Given(value) => self.handle_coerced_expression(value, &field.typ),
+            FieldValue::Base(base_expression) => {
+                let Some(location) = base else {
+                    unreachable!("The base is stored before its fields are read");
+                };
+
+                let pos = &base_expression.pos;
+                let typ = VariableType::Pointer(Box::new(self.infer_type(base_expression)?));
+
+                Ok(Self::get_push_procedures(&typ, location, false)
+                    .into_iter()
+                    .fold(Builder::new(), |builder, kind| {
+                        builder.push(Procedure::new(pos.clone(), kind))
+                    })
+                    .push(Procedure::new(
+                        pos.clone(),
+                        ProcedureKind::Push(OperandValue::Int(field.offset as i32)),
+                    ))
+                    .push(Procedure::new(
+                        pos.clone(),
+                        ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+                    ))
+                    .append(Self::get_load(&field.typ, pos)))
+            }
+            FieldValue::Default(values) => {
+                Ok(values.iter().fold(Builder::new(), |builder, value| {
+                    builder.push(Procedure::new(
+                        expression.pos.clone(),
+                        ProcedureKind::Push(value.clone()),
+                    ))
+                }))
+            }
+        }
+    }
+
+    /// Whether the field starts within a slot and continues into the next one.
+    pub fn is_crossing_field(offset: usize, size: usize) -> bool {
+        !offset.is_multiple_of(8) && offset % 8 + size > 8
+    }
+
+    /// Pushes the value of a field at `offset` after the fields before it.
+    /// A field starting within a slot is added to the earlier fields sharing that slot.
+    /// Fields crossing a slot boundary can not be packed this way, see `get_assigned_fields`.
+    pub fn append_field(
+        builder: Builder,
+        value: Builder,
+        pos: &Range<usize>,
+        offset: usize,
+        size: usize,
+    ) -> Builder {
+        let within_slot = offset % 8;
+        let builder = builder.append(Self::get_packed_value(value, pos, size, within_slot));
+
+        // The slot was started by an earlier field
+        if within_slot == 0 {
+            return builder;
+        }
+
+        builder.push(Procedure::new(
+            pos.clone(),
+            ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+        ))
+    }
+
+    /// Assigns the fields one by one to a temporary variable, and pushes its value.
+    /// Used for values with fields crossing a slot boundary,
+    /// whose bytes are spread over several slots of the value.
+    pub fn get_assigned_fields(
+        &mut self,
+        expression: &Expression,
+        typ: &VariableType,
+        fields: Vec<(Builder, usize, usize)>, // Value, offset and size of each field
+    ) -> Builder {
+        let location = self.create_temporary(".construction", typ, expression.pos.clone());
+        let push = |builder: Builder, kind: ProcedureKind| {
+            builder.push(Procedure::new(expression.pos.clone(), kind))
+        };
+
+        let mut builder = Builder::new();
+
+        for (value, offset, size) in fields {
+            let address = Self::get_push_procedures(typ, &location, true);
+            let field_address = [
+                ProcedureKind::Push(OperandValue::Int(offset as i32)),
+                ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+            ];
+
+            builder = address
+                .into_iter()
+                .chain(field_address)
+                .fold(builder, push)
+                .append(value);
+            builder = push(
+                builder,
+                ProcedureKind::Assign(Assign {
+                    location: VariableLocation::Address,
+                    size,
+                }),
+            );
+        }
+
+        Self::get_push_procedures(typ, &location, false)
+            .into_iter()
+            .fold(builder, push)
+    }
+
+    /// Pushes the fields in declaration order,
+    /// fields sharing a slot are packed together like in memory.
+    pub fn handle_struct_construction(
+        &mut self,
+        expression: &Expression,
+        sconst: &StructConstruction,
+    ) -> Result<Builder, CompilerError> {
+        let fields = self.get_struct_construction_fields(sconst)?;
+
+        let (mut builder, base) = match &sconst.base {
+            Some(base)
+                if fields
+                    .iter()
+                    .any(|field| matches!(field.value, FieldValue::Base(_))) =>
+            {
+                let (builder, location) = self.handle_construction_base(base)?;
+                (builder, Some(location))
+            }
+            _ => (Builder::new(), None),
+        };
+
+        if fields
+            .iter()
+            .any(|field| Self::is_crossing_field(field.offset, field.size))
+        {
+            let typ = self.infer_type(expression)?;
+            let mut values = Vec::new();
+
+            for field in &fields {
+                let value = self.handle_construction_field(expression, field, base.as_ref())?;
+                values.push((value, field.offset, field.size));
             }
 
-            field_content.push((field_identifier.clone(), field_const));
+            return Ok(builder.append(self.get_assigned_fields(expression, &typ, values)));
         }
 
-        for (_field_identifier, field_const) in field_content {
-            builder = builder.append(self.handle_expression(&field_const.expr)?);
+        for field in fields {
+            let value = self.handle_construction_field(expression, &field, base.as_ref())?;
+            builder = Self::append_field(builder, value, &expression.pos, field.offset, field.size);
         }
 
         Ok(builder)
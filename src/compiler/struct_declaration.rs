@@ -1,11 +1,11 @@
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 
-use crate::parser::definition::{Declaration, StructDeclaration};
+use crate::parser::definition::{Declaration, Expression, StructDeclaration};
 
 use super::{
     builder::Builder,
-    definition::{Struct, StructField},
-    error::CompilerError,
+    definition::{OperandValue, Struct, StructField, StructLayout},
+    error::{CompilerError, CompilerErrorKind},
     program::Program,
     scope::{
         variable::{StructType, Variable, VariableType},
@@ -14,6 +14,60 @@ use super::{
 };
 
 impl Program {
+    /// Lays out fields of the given types like C does, in order with every field
+    /// at a multiple of its alignment. The first field ends up at the lowest address.
+    pub fn get_struct_layout(types: &[VariableType]) -> StructLayout {
+        let mut offsets = Vec::with_capacity(types.len());
+        let mut offset: usize = 0;
+        let mut alignment = 1;
+
+        for typ in types {
+            let field_alignment = Self::get_type_alignment(typ);
+
+            offset = offset.next_multiple_of(field_alignment);
+            alignment = alignment.max(field_alignment);
+
+            offsets.push(offset);
+            offset += Self::get_type_size(typ);
+        }
+
+        StructLayout {
+            offsets,
+            // Tail padding, so the fields stay aligned in arrays of the struct
+            size: offset.next_multiple_of(alignment),
+            alignment,
+        }
+    }
+
+    /// Default values are evaluated at compile time, like static variables.
+    fn get_field_default(
+        &mut self,
+        identifier: &str,
+        typ: &VariableType,
+        default: &Expression,
+    ) -> Result<Vec<OperandValue>, CompilerError> {
+        if !self.is_static_expression(default) {
+            return Err(CompilerError::new(
+                default.pos.clone(),
+                CompilerErrorKind::NonStaticDefault(identifier.to_string()),
+            ));
+        }
+
+        let default_type = self.infer_type(default)?;
+        if !Self::is_assignable(&default_type, typ) {
+            return Err(CompilerError::new(
+                default.pos.clone(),
+                CompilerErrorKind::WrongType {
+                    got: default_type,
+                    expected: typ.clone(),
+                    expected_alias: None,
+                },
+            ));
+        }
+
+        self.get_static_data(default)
+    }
+
     pub fn handle_struct_declaration(
         &mut self,
         statement: &Declaration,
@@ -23,32 +77,41 @@ impl Program {
             todo!("Must declare struct in a root scope");
         };
 
-        let mut fields = BTreeMap::new();
-        let mut offset = 0;
+        let types = sdec
+            .fields
+            .iter()
+            .map(|(_, field)| self.get_variable_type(&field.typ))
+            .collect::<Result<Vec<_>, _>>()?;
+        let layout = Self::get_struct_layout(&types);
+
+        let mut fields = Vec::with_capacity(sdec.fields.len());
 
-        // Do it in reverse since the stack grows that way
-        for (identifier, field) in sdec.fields.iter().rev() {
-            let typ = self.get_variable_type(&field.typ);
-            let size = Self::get_type_size(&typ);
+        for (((identifier, field), typ), offset) in
+            sdec.fields.iter().zip(types).zip(layout.offsets)
+        {
+            let default = match &field.default {
+                Some(default) => Some(self.get_field_default(identifier, &typ, default)?),
+                None => None,
+            };
 
-            fields.insert(
+            fields.push((
                 identifier.clone(),
                 StructField {
+                    size: Self::get_type_size(&typ),
                     typ,
                     offset,
-                    size,
                     pos: field.pos.clone(),
+                    default,
                 },
-            );
-
-            offset += size;
+            ));
         }
 
-        let struct_size = fields.values().map(|field| field.size).sum();
-
         self.structs.push(Struct {
             fields,
-            size: offset,
+            size: layout.size,
+            identifier_pos: sdec.identifier_pos.clone(),
+            methods: HashMap::new(),
+            traits: Vec::new(),
         });
 
         let struct_id = self.structs.len() - 1;
@@ -61,8 +124,11 @@ impl Program {
                 typ: VariableType::Struct(StructType {
                     id: struct_id,
                     identifier: sdec.identifier.clone(),
-                    size: struct_size,
+                    size: layout.size,
+                    alignment: layout.alignment,
+                    type_parameter: None,
                 }),
+                mutable: false,
             },
         );
 
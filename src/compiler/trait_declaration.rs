@@ -0,0 +1,48 @@
+use crate::parser::definition::TraitDeclaration;
+
+use super::{
+    builder::Builder,
+    definition::Trait,
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::ProgramScope,
+};
+
+impl Program {
+    pub fn register_trait(&mut self, tdec: &TraitDeclaration) -> Result<(), CompilerError> {
+        if let Some(existing) = self.traits.get(&tdec.identifier) {
+            return Err(CompilerError::new(
+                tdec.identifier_pos.clone(),
+                CompilerErrorKind::RedeclaredTrait(
+                    tdec.identifier.clone(),
+                    existing.identifier_pos.clone(),
+                ),
+            ));
+        }
+
+        self.traits.insert(
+            tdec.identifier.clone(),
+            Trait {
+                identifier_pos: tdec.identifier_pos.clone(),
+                methods: tdec.methods.clone(),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn handle_trait_declaration(
+        &mut self,
+        tdec: &TraitDeclaration,
+    ) -> Result<Builder, CompilerError> {
+        // Traits in the root scope were registered before compilation
+        let ProgramScope::RootScope(_) = &mut self.scope else {
+            return Err(CompilerError::new(
+                tdec.identifier_pos.clone(),
+                CompilerErrorKind::NestedDeclaration("Traits"),
+            ));
+        };
+
+        Ok(Builder::new())
+    }
+}
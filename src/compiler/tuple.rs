@@ -0,0 +1,144 @@
+use crate::parser::definition::{
+    Expression, ExpressionKind, MemberAccess, Primary, TupleDeclaration, VariableDeclaration,
+};
+
+use super::{
+    builder::Builder,
+    definition::StructField,
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::VariableType,
+};
+
+/// Tuples are laid out like structs, with the fields `0`, `1`, ...
+impl Program {
+    pub fn get_tuple_field(
+        expression: &Expression,
+        access: &MemberAccess,
+        types: &[VariableType],
+    ) -> Result<StructField, CompilerError> {
+        let index = access
+            .member
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < types.len());
+
+        let Some(index) = index else {
+            return Err(CompilerError::new(
+                expression.pos.clone(),
+                CompilerErrorKind::UndefinedField {
+                    identifier: VariableType::Tuple(types.to_vec()).to_string(),
+                    field: access.member.clone(),
+                },
+            ));
+        };
+
+        let typ = types[index].clone();
+
+        Ok(StructField {
+            offset: Self::get_struct_layout(types).offsets[index],
+            size: Self::get_type_size(&typ),
+            typ,
+            // Tuples are not declared anywhere
+            pos: access.left.pos.clone(),
+            default: None,
+        })
+    }
+
+    pub fn handle_tuple(
+        &mut self,
+        expression: &Expression,
+        elements: &[Expression],
+    ) -> Result<Builder, CompilerError> {
+        let VariableType::Tuple(types) = self.infer_type(expression)? else {
+            unreachable!()
+        };
+
+        let layout = Self::get_struct_layout(&types);
+
+        if layout
+            .offsets
+            .iter()
+            .zip(&types)
+            .any(|(offset, typ)| Self::is_crossing_field(*offset, Self::get_type_size(typ)))
+        {
+            let mut values = Vec::new();
+
+            for ((element, typ), offset) in elements.iter().zip(&types).zip(layout.offsets) {
+                let value = self.handle_expression(element)?;
+                values.push((value, offset, Self::get_type_size(typ)));
+            }
+
+            let typ = VariableType::Tuple(types);
+            return Ok(self.get_assigned_fields(expression, &typ, values));
+        }
+
+        let mut builder = Builder::new();
+
+        for ((element, typ), offset) in elements.iter().zip(&types).zip(layout.offsets) {
+            let value = self.handle_expression(element)?;
+            builder = Self::append_field(
+                builder,
+                value,
+                &element.pos,
+                offset,
+                Self::get_type_size(typ),
+            );
+        }
+
+        Ok(builder)
+    }
+
+    /// Declares the hidden variable holding the tuple,
+    /// and then every binding as if it was declared as `let a = tuple.0;`.
+    pub fn handle_tuple_declaration(
+        &mut self,
+        declaration: &TupleDeclaration,
+    ) -> Result<Builder, CompilerError> {
+        let value = &declaration.value;
+        let right = value
+            .right
+            .as_ref()
+            .expect("Tuples are always destructured from a value");
+
+        let typ = self.infer_type(right)?;
+        if !matches!(&typ, VariableType::Tuple(types) if types.len() == declaration.bindings.len())
+        {
+            return Err(CompilerError::new(
+                right.pos.clone(),
+                CompilerErrorKind::TupleBindings {
+                    typ,
+                    bindings: declaration.bindings.len(),
+                },
+            ));
+        }
+
+        let mut builder = self.handle_variable_declaration(value)?;
+
+        for (index, binding) in declaration.bindings.iter().enumerate() {
+            let pos = binding.identifier_pos.clone();
+            let element = Expression {
+                pos: pos.clone(),
+                kind: ExpressionKind::MemberAccess(MemberAccess {
+                    left: Box::new(Expression {
+                        pos: value.identifier_pos.clone(),
+                        kind: ExpressionKind::Primary(Primary::Identifier(
+                            value.identifier.clone(),
+                        )),
+                    }),
+                    member: index.to_string(),
+                }),
+            };
+
+            builder = builder.append(self.handle_variable_declaration(&VariableDeclaration {
+                typ: None,
+                identifier: binding.identifier.clone(),
+                mutable: binding.mutable,
+                identifier_pos: pos,
+                right: Some(Box::new(element)),
+            })?);
+        }
+
+        Ok(builder)
+    }
+}
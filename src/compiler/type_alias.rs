@@ -0,0 +1,50 @@
+use crate::parser::definition::{Declaration, TypeAlias};
+
+use super::{
+    builder::Builder,
+    definition,
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+};
+
+impl Program {
+    /// Makes the alias available for type lookups.
+    /// Aliases are not resolved until they are used.
+    pub fn register_type_alias(&mut self, alias: &TypeAlias) -> Result<(), CompilerError> {
+        if let Some(existing) = self.type_aliases.get(&alias.identifier) {
+            return Err(CompilerError::new(
+                alias.identifier_pos.clone(),
+                CompilerErrorKind::RedeclaredVariable(
+                    alias.identifier.clone(),
+                    existing.pos.clone(),
+                ),
+            ));
+        }
+
+        self.type_aliases.insert(
+            alias.identifier.clone(),
+            definition::TypeAlias {
+                typ: alias.typ.clone(),
+                pos: alias.identifier_pos.clone(),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn handle_type_alias(
+        &mut self,
+        _declaration: &Declaration,
+        alias: &TypeAlias,
+    ) -> Result<Builder, CompilerError> {
+        // Aliases in the root scope were registered before compilation
+        if !self.type_aliases.contains_key(&alias.identifier) {
+            self.register_type_alias(alias)?;
+        }
+
+        // Resolve once to report undefined types and cycles
+        self.get_variable_type(&alias.typ)?;
+
+        Ok(Builder::new())
+    }
+}
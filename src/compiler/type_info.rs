@@ -0,0 +1,91 @@
+use crate::parser::{
+    definition::{Expression, TypeInfo, TypeInfoArgument},
+    types::{Type, TypeIdentifier},
+};
+
+use super::{
+    builder::Builder,
+    definition::{OperandValue, Procedure, ProcedureKind},
+    error::{CompilerError, CompilerErrorKind},
+    program::Program,
+    scope::variable::VariableType,
+};
+
+impl Program {
+    fn get_type_info_argument_type(
+        &self,
+        argument: &TypeInfoArgument,
+    ) -> Result<VariableType, CompilerError> {
+        match argument {
+            TypeInfoArgument::Type(typ) => self.get_variable_type(typ),
+            TypeInfoArgument::Expression(expr) => match expr.get_string() {
+                // Struct names are variables themselves, so only aliases need resolving
+                Some(identifier)
+                    if self.get_variable(&identifier.to_string()).is_none()
+                        && self.type_aliases.contains_key(identifier) =>
+                {
+                    self.get_variable_type(&Type {
+                        identifier: TypeIdentifier::Struct(identifier.to_string()),
+                        pos: expr.pos.clone(),
+                        children: None,
+                    })
+                }
+                _ => self.infer_type(expr),
+            },
+        }
+    }
+
+    /// Evaluates the type info at compile time, the result is in bytes.
+    /// The expression given to `sizeof` or `alignof` is never evaluated.
+    pub fn get_type_info_value(&self, info: &TypeInfo) -> Result<usize, CompilerError> {
+        match info {
+            TypeInfo::Size(argument) => Ok(Self::get_type_size(
+                &self.get_type_info_argument_type(argument)?,
+            )),
+            TypeInfo::Align(argument) => Ok(Self::get_type_alignment(
+                &self.get_type_info_argument_type(argument)?,
+            )),
+            TypeInfo::Offset {
+                typ,
+                field,
+                field_pos,
+            } => {
+                let struct_type = match self.get_variable_type(typ)? {
+                    VariableType::Struct(struct_type) => struct_type,
+                    other => {
+                        return Err(CompilerError::new(
+                            typ.pos.clone(),
+                            CompilerErrorKind::ExpectedStruct(other),
+                        ))
+                    }
+                };
+
+                let Some(struct_field) = self.structs.get(struct_type.id).unwrap().get_field(field)
+                else {
+                    return Err(CompilerError::new(
+                        field_pos.clone(),
+                        CompilerErrorKind::UndefinedField {
+                            identifier: struct_type.identifier,
+                            field: field.clone(),
+                        },
+                    ));
+                };
+
+                Ok(struct_field.offset)
+            }
+        }
+    }
+
+    pub fn handle_type_info(
+        &mut self,
+        expression: &Expression,
+        info: &TypeInfo,
+    ) -> Result<Builder, CompilerError> {
+        let value = self.get_type_info_value(info)?;
+
+        Ok(Builder::new().push(Procedure::new(
+            expression.pos.clone(),
+            ProcedureKind::Push(OperandValue::Int(value as i32)),
+        )))
+    }
+}
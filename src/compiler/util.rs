@@ -1,14 +1,16 @@
+use std::{collections::HashMap, ops::Range};
+
 use crate::{
     lexer::{Keyword, Literal},
     parser::{
-        definition::{Declaration, Expression, ExpressionKind, Primary},
+        definition::{Declaration, Expression, ExpressionKind, MemberAccess, Primary},
         types::{Type, TypeIdentifier},
     },
 };
 
 use super::{
     builder::Builder,
-    definition::StructField,
+    definition::{GlobalData, StructField},
     error::{CompilerError, CompilerErrorKind},
     program::Program,
     scope::{
@@ -33,13 +35,51 @@ impl Program {
         }
     }
 
+    /// Only looks for the variable in the innermost scope.
+    pub fn get_local_variable(&self, identifier: &String) -> Option<&StoredVariable> {
+        match &self.scope {
+            ProgramScope::RootScope(scope) => scope.scope.get_local_variable(identifier),
+            ProgramScope::FunctionScope(scope) => scope.scope.get_local_variable(identifier),
+        }
+    }
+
     /// Creates a variable in the current scope.
     /// Returns location of the created variable.
     pub fn create_variable(&mut self, identifier: String, variable: Variable) -> VariableLocation {
         match &mut self.scope {
             ProgramScope::RootScope(scope) => scope.create_variable(identifier, variable),
-            ProgramScope::FunctionScope(scope) => scope.create_variable(identifier, variable),
+            ProgramScope::FunctionScope(scope) => {
+                let slots = Self::get_type_slots(&variable.typ);
+                scope.create_variable(identifier, variable, slots)
+            }
+        }
+    }
+
+    /// Creates a hidden variable holding an intermediate value of the type.
+    /// Outside of functions its memory is reserved in the bss section.
+    pub fn create_temporary(
+        &mut self,
+        identifier: &str,
+        typ: &VariableType,
+        pos: Range<usize>,
+    ) -> VariableLocation {
+        let location = self.create_variable(
+            identifier.to_string(),
+            Variable {
+                pos,
+                typ: typ.clone(),
+                mutable: true,
+            },
+        );
+
+        if let VariableLocation::Global(label) = &location {
+            self.global_data.insert(
+                label.clone(),
+                GlobalData::Reserved(Self::get_type_slots(typ)),
+            );
         }
+
+        location
     }
 
     fn get_primitive_type_size(primitive: &Keyword) -> usize {
@@ -54,10 +94,36 @@ impl Program {
     // todo: maybe this is different depending on platform?
     pub fn get_type_size(typ: &VariableType) -> usize {
         match typ {
-            VariableType::Pointer(_) => 8,
+            VariableType::Pointer(_) | VariableType::Nullable(_) | VariableType::Null => 8,
+            VariableType::Const(typ) => Self::get_type_size(typ),
+            VariableType::Array(typ, length) => Self::get_type_size(typ) * length,
+            VariableType::Slice(_) | VariableType::Dyn(_) => 16,
             VariableType::Value(typ) => Self::get_primitive_type_size(typ),
             VariableType::Function(_) => todo!("Not supported"),
+            VariableType::FunctionPointer(..) => 8,
             VariableType::Struct(s) => s.size,
+            VariableType::Tuple(types) => Self::get_struct_layout(types).size,
+        }
+    }
+
+    /// Return alignment in bytes
+    pub fn get_type_alignment(typ: &VariableType) -> usize {
+        match typ {
+            VariableType::Struct(s) => s.alignment,
+            VariableType::Tuple(types) => Self::get_struct_layout(types).alignment,
+            VariableType::Array(typ, _) => Self::get_type_alignment(typ),
+            VariableType::Const(typ) => Self::get_type_alignment(typ),
+            VariableType::Slice(_) | VariableType::Dyn(_) => 8,
+            _ => Self::get_type_size(typ),
+        }
+    }
+
+    /// Amount of 8 byte slots needed to store the type,
+    /// types smaller than 8 bytes still take up a whole slot.
+    pub fn get_type_slots(typ: &VariableType) -> usize {
+        match typ {
+            VariableType::Function(_) => 1,
+            _ => Self::get_type_size(typ).div_ceil(8).max(1),
         }
     }
 
@@ -81,14 +147,15 @@ impl Program {
                         None
                     }
                 }
-                Keyword::LessThan | Keyword::GreaterThan | Keyword::Equality => {
-                    match (*left, *right) {
-                        (Keyword::Int | Keyword::Char, Keyword::Int | Keyword::Char) => {
-                            Some(VariableType::Value(Keyword::Bool))
-                        }
-                        _ => None,
+                Keyword::LessThan
+                | Keyword::GreaterThan
+                | Keyword::Equality
+                | Keyword::NotEqual => match (*left, *right) {
+                    (Keyword::Int | Keyword::Char, Keyword::Int | Keyword::Char) => {
+                        Some(VariableType::Value(Keyword::Bool))
                     }
-                }
+                    _ => None,
+                },
                 _ => None,
             },
             (VariableType::Pointer(left), VariableType::Pointer(right)) => match operator {
@@ -99,7 +166,10 @@ impl Program {
                         None
                     }
                 }
-                Keyword::LessThan | Keyword::GreaterThan | Keyword::Equality => {
+                Keyword::LessThan
+                | Keyword::GreaterThan
+                | Keyword::Equality
+                | Keyword::NotEqual => {
                     if left == right {
                         Some(VariableType::Value(Keyword::Bool))
                     } else {
@@ -108,13 +178,74 @@ impl Program {
                 }
                 _ => None,
             },
+            // Pointers which may be null can only be compared
+            (
+                VariableType::Pointer(left) | VariableType::Nullable(left),
+                VariableType::Pointer(right) | VariableType::Nullable(right),
+            ) if left == right && matches!(operator, Keyword::Equality | Keyword::NotEqual) => {
+                Some(VariableType::Value(Keyword::Bool))
+            }
+            (
+                VariableType::Pointer(_) | VariableType::Nullable(_) | VariableType::Null,
+                VariableType::Pointer(_) | VariableType::Nullable(_) | VariableType::Null,
+            ) if matches!(operator, Keyword::Equality | Keyword::NotEqual) => {
+                Some(VariableType::Value(Keyword::Bool))
+            }
             _ => None,
         }
     }
 
-    pub fn get_variable_type(&self, typ: &Type) -> VariableType {
+    /// Whether a value of type `got` can be stored where `expected` is required.
+    /// Pointers and slices may implicitly be converted to pointers and slices of const,
+    /// and pointers to pointers which may be null.
+    pub fn is_assignable(got: &VariableType, expected: &VariableType) -> bool {
+        match (got, expected) {
+            (VariableType::Null, VariableType::Pointer(_) | VariableType::Nullable(_)) => true,
+            (VariableType::Pointer(got), VariableType::Pointer(expected))
+            | (
+                VariableType::Pointer(got) | VariableType::Nullable(got),
+                VariableType::Nullable(expected),
+            )
+            | (VariableType::Slice(got), VariableType::Slice(expected)) => {
+                got == expected
+                    || matches!(expected.as_ref(), VariableType::Const(inner) if inner == got)
+            }
+            _ => got == expected,
+        }
+    }
+
+    /// Returns the alias name if the type refers to a type alias.
+    pub fn get_type_alias(&self, typ: &Type) -> Option<String> {
         match typ.identifier {
-            TypeIdentifier::Primitive(Keyword::Pointer) => {
+            TypeIdentifier::Struct(ref s) if self.type_aliases.contains_key(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_variable_type(&self, typ: &Type) -> Result<VariableType, CompilerError> {
+        self.resolve_variable_type(typ, &mut Vec::new(), &self.type_parameters)
+    }
+
+    /// Resolves the type with the given types for `Self` and type parameters,
+    /// instead of those of the function being compiled.
+    pub fn get_generic_type(
+        &self,
+        typ: &Type,
+        type_parameters: &HashMap<String, VariableType>,
+    ) -> Result<VariableType, CompilerError> {
+        self.resolve_variable_type(typ, &mut Vec::new(), type_parameters)
+    }
+
+    /// Resolves the type, keeping track of the aliases
+    /// currently being resolved in order to detect cycles.
+    fn resolve_variable_type(
+        &self,
+        typ: &Type,
+        aliases: &mut Vec<String>,
+        type_parameters: &HashMap<String, VariableType>,
+    ) -> Result<VariableType, CompilerError> {
+        Ok(match typ.identifier {
+            TypeIdentifier::Primitive(Keyword::Pointer) | TypeIdentifier::Nullable => {
                 let Some(children) = &typ.children else {
                     todo!("Pointer type requires 1 child type");
                 };
@@ -123,12 +254,125 @@ impl Program {
                     todo!("Wrong amount of type arguments for pointer type");
                 }
 
-                let inner = children.get(0).unwrap();
-                VariableType::Pointer(Box::new(self.get_variable_type(inner)))
+                let inner = children.first().unwrap();
+                let inner =
+                    Box::new(self.resolve_variable_type(inner, aliases, type_parameters)?);
+
+                match typ.identifier {
+                    TypeIdentifier::Nullable => VariableType::Nullable(inner),
+                    _ => VariableType::Pointer(inner),
+                }
+            }
+            TypeIdentifier::Primitive(Keyword::Const) => {
+                let inner = typ.children.as_ref().and_then(|children| children.first());
+                let inner = inner.expect("Const type is only parsed with a child type");
+
+                VariableType::Const(Box::new(self.resolve_variable_type(
+                    inner,
+                    aliases,
+                    type_parameters,
+                )?))
             }
             TypeIdentifier::Primitive(primitive) => VariableType::Value(primitive),
-            TypeIdentifier::Struct(ref s) => self.get_variable(s).unwrap().typ.clone(),
-        }
+            TypeIdentifier::Array(length) => {
+                let inner = typ.children.as_ref().and_then(|children| children.first());
+                let inner = inner.expect("Array type is only parsed with a child type");
+
+                VariableType::Array(
+                    Box::new(self.resolve_variable_type(inner, aliases, type_parameters)?),
+                    length,
+                )
+            }
+            TypeIdentifier::Slice => {
+                let inner = typ.children.as_ref().and_then(|children| children.first());
+                let inner = inner.expect("Slice type is only parsed with a child type");
+
+                VariableType::Slice(Box::new(self.resolve_variable_type(
+                    inner,
+                    aliases,
+                    type_parameters,
+                )?))
+            }
+            TypeIdentifier::Function(ref return_type) => VariableType::FunctionPointer(
+                typ.children
+                    .iter()
+                    .flatten()
+                    .map(|parameter| {
+                        self.resolve_variable_type(parameter, aliases, type_parameters)
+                    })
+                    .collect::<Result<_, _>>()?,
+                return_type
+                    .as_ref()
+                    .map(|return_type| {
+                        self.resolve_variable_type(return_type, aliases, type_parameters)
+                    })
+                    .transpose()?
+                    .map(Box::new),
+            ),
+            TypeIdentifier::Dyn(ref trait_identifier) => {
+                if !self.traits.contains_key(trait_identifier) {
+                    return Err(CompilerError::new(
+                        typ.pos.clone(),
+                        CompilerErrorKind::UndefinedTrait(trait_identifier.clone()),
+                    ));
+                }
+
+                VariableType::Dyn(trait_identifier.clone())
+            }
+            TypeIdentifier::Tuple => VariableType::Tuple(
+                typ.children
+                    .iter()
+                    .flatten()
+                    .map(|element| self.resolve_variable_type(element, aliases, type_parameters))
+                    .collect::<Result<_, _>>()?,
+            ),
+            TypeIdentifier::Struct(ref s) => {
+                if let Some(typ) = type_parameters.get(s) {
+                    typ.clone()
+                } else if let Some(alias) = self.type_aliases.get(s) {
+                    if aliases.contains(s) {
+                        return Err(CompilerError::new(
+                            alias.pos.clone(),
+                            CompilerErrorKind::CyclicTypeAlias(s.clone()),
+                        ));
+                    }
+
+                    aliases.push(s.clone());
+                    let resolved =
+                        self.resolve_variable_type(&alias.typ, aliases, type_parameters)?;
+                    aliases.pop();
+
+                    resolved
+                } else {
+                    match self.get_variable(s).map(|variable| &variable.typ) {
+                        Some(typ @ VariableType::Struct(_)) => typ.clone(),
+                        _ => {
+                            return Err(CompilerError::new(
+                                typ.pos.clone(),
+                                CompilerErrorKind::UndefinedType(s.clone()),
+                            ))
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// The type of a function when used as a value.
+    pub fn get_function_pointer_type(
+        &self,
+        function_id: usize,
+    ) -> Result<VariableType, CompilerError> {
+        let function = self.functions.get(function_id).unwrap();
+
+        Ok(VariableType::FunctionPointer(
+            function
+                .parameters
+                .iter()
+                .map(|parameter| parameter.typ.clone())
+                .collect(),
+            function.return_type.clone().map(Box::new),
+        ))
     }
 
     pub fn infer_type(&self, expr: &Expression) -> Result<VariableType, CompilerError> {
@@ -142,11 +386,17 @@ impl Program {
                         ));
                     };
 
-                    Ok(variable.typ.clone())
+                    match variable.typ {
+                        VariableType::Function(function_id) => {
+                            self.get_function_pointer_type(function_id)
+                        }
+                        _ => Ok(self.get_narrowed_type(variable)),
+                    }
                 }
                 Primary::Literal(literal) => Ok(match literal {
                     Literal::Int(_) => VariableType::Value(Keyword::Int),
                     Literal::Bool(_) => VariableType::Value(Keyword::Bool),
+                    Literal::Null => VariableType::Null,
                     Literal::String(_) => {
                         VariableType::Pointer(Box::new(VariableType::Value(Keyword::Char)))
                     }
@@ -156,7 +406,13 @@ impl Program {
                 let expr_type = self.infer_type(&index.left)?;
 
                 match expr_type {
-                    VariableType::Pointer(pointer_type) => Ok(*pointer_type),
+                    VariableType::Pointer(pointer_type) => Ok(pointer_type.without_const()),
+                    VariableType::Array(element_type, _) => Ok(*element_type),
+                    VariableType::Slice(element_type) => Ok(element_type.without_const()),
+                    VariableType::Nullable(_) => Err(CompilerError::new(
+                        index.left.pos.clone(),
+                        CompilerErrorKind::NullableDereference(expr_type),
+                    )),
                     _ => Err(CompilerError::new(
                         index.left.pos.clone(),
                         CompilerErrorKind::DereferenceNonPointer(expr_type),
@@ -169,6 +425,8 @@ impl Program {
                 match unary.operator {
                     Keyword::Ampersand => Ok(VariableType::Pointer(Box::new(expr_type))),
                     Keyword::Asterix => {
+                        Self::check_not_nullable(unary.expr.pos.clone(), &expr_type)?;
+
                         let VariableType::Pointer(typ) = expr_type else {
                             return Err(CompilerError::new(
                                 unary.operator_pos.clone(),
@@ -176,7 +434,7 @@ impl Program {
                             ));
                         };
 
-                        Ok(*typ)
+                        Ok(typ.without_const())
                     }
                     _ => todo!("Not supported"),
                 }
@@ -201,52 +459,85 @@ impl Program {
                 Ok(typ)
             }
             ExpressionKind::FunctionCall(call) => {
-                let identifier = call.left.get_string().unwrap().to_string();
-
-                let Some(function) = self.get_variable(&identifier) else {
-                    return Err(CompilerError::new(
-                        call.left.pos.clone(),
-                        CompilerErrorKind::UndefinedFunction(identifier),
-                    ));
-                };
-
-                let VariableType::Function(function_id) = function.typ else {
-                    todo!("Variable is not a function");
-                };
-
-                let Some(return_type) = &self.functions.get(function_id).unwrap().return_type else {
+                let Some(return_type) = self.get_return_type(call)? else {
                     todo!("No return type for function");
                 };
 
-                Ok(return_type.clone())
+                Ok(return_type)
+            }
+            ExpressionKind::Cast(cast) => self.infer_cast_type(cast),
+            ExpressionKind::ArraySlice(slice) => self.infer_slice_type(expr, slice),
+            ExpressionKind::ArrayLiteral(array) => self.infer_array_literal_type(expr, array),
+            ExpressionKind::TypeInfo(info) => {
+                self.get_type_info_value(info)?;
+                Ok(VariableType::Value(Keyword::Int))
             }
             ExpressionKind::StructConstruction(sconst) => {
                 Ok(self.get_variable(&sconst.identifier).unwrap().typ.clone())
             }
-            ExpressionKind::MemberAccess(access) => Ok(self
-                .get_struct_field_type(&access.left, &access.member)?
-                .typ
-                .clone()),
+            ExpressionKind::MemberAccess(access)
+                if matches!(self.infer_type(&access.left)?, VariableType::Slice(_)) =>
+            {
+                self.check_slice_member(expr, access)?;
+                Ok(VariableType::Value(Keyword::Int))
+            }
+            ExpressionKind::MemberAccess(access) => {
+                Ok(self.get_struct_field_type(expr, access)?.typ)
+            }
+            ExpressionKind::Tuple(elements) => Ok(VariableType::Tuple(
+                elements
+                    .iter()
+                    .map(|element| self.infer_type(element))
+                    .collect::<Result<_, _>>()?,
+            )),
         }
     }
 
+    /// Member access on a pointer to a struct dereferences the pointer first.
+    pub fn is_auto_deref(&self, access: &MemberAccess) -> Result<bool, CompilerError> {
+        Ok(matches!(
+            self.infer_type(&access.left)?,
+            VariableType::Pointer(_)
+        ))
+    }
+
     pub fn get_struct_field_type(
         &self,
-        struct_value: &Expression,
-        member: &str,
-    ) -> Result<&StructField, CompilerError> {
-        let VariableType::Struct(struct_type) = self.infer_type(struct_value)? else {
-            todo!("Struct does not exist: {:#?}\n{:#?}", struct_value, self.infer_type(struct_value)?);
+        expression: &Expression,
+        access: &MemberAccess,
+    ) -> Result<StructField, CompilerError> {
+        let typ = match self.infer_type(&access.left)? {
+            VariableType::Pointer(inner) => *inner,
+            typ => typ,
+        };
+
+        Self::check_not_nullable(access.left.pos.clone(), &typ)?;
+
+        let struct_type = match typ.clone().without_const() {
+            VariableType::Struct(struct_type) => struct_type,
+            VariableType::Tuple(types) => return Self::get_tuple_field(expression, access, &types),
+            _ => {
+                return Err(CompilerError::new(
+                    access.left.pos.clone(),
+                    CompilerErrorKind::ExpectedStruct(typ),
+                ))
+            }
         };
 
         let Some(struct_declaration) = self.structs.get(struct_type.id) else {
             todo!("Struct was not found in structs field");
         };
 
-        let Some(field_type) = struct_declaration.fields.get(member) else {
-            todo!("Field was not found");
+        let Some(field_type) = struct_declaration.get_field(&access.member) else {
+            return Err(CompilerError::new(
+                expression.pos.clone(),
+                CompilerErrorKind::UndefinedField {
+                    identifier: struct_type.identifier,
+                    field: access.member.clone(),
+                },
+            ));
         };
 
-        Ok(field_type)
+        Ok(field_type.clone())
     }
 }
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use crate::{
+    lexer::Keyword,
+    parser::{
+        definition::{Expression, MemberAccess, TraitMethod},
+        types::{Type, TypeIdentifier},
+    },
+};
+
+use super::{
+    builder::Builder,
+    definition::{
+        Arithmetic, Assign, GlobalData, OperandValue, Procedure, ProcedureKind, RegisterSize,
+    },
+    error::{CompilerError, CompilerErrorKind},
+    function_call::Parameter,
+    program::Program,
+    scope::variable::{StructType, VariableLocation, VariableType},
+};
+
+/// A method called through a `&dyn Trait`.
+pub struct DynMethod {
+    // Position of the method in the vtable
+    pub index: usize,
+
+    // Parameters after `self`
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<VariableType>,
+}
+
+/// A `&dyn Trait` is a pointer to the value, followed by a pointer to the vtable
+/// of its type for the trait. The vtable holds the methods of the trait in declaration order.
+impl Program {
+    /// The struct and trait, if a value of type `got` is turned into `expected` by adding a vtable.
+    fn get_dyn_coercion(
+        &self,
+        got: &VariableType,
+        expected: &VariableType,
+    ) -> Option<(StructType, String)> {
+        let (VariableType::Pointer(inner), VariableType::Dyn(trait_identifier)) = (got, expected)
+        else {
+            return None;
+        };
+
+        let typ = inner.as_ref().clone().without_const();
+        match &typ {
+            VariableType::Struct(struct_type) if self.implements(&typ, trait_identifier) => {
+                Some((struct_type.clone(), trait_identifier.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a value of type `got` can be used where `expected` is expected,
+    /// either as it is or as a `&dyn Trait`.
+    pub fn is_coercible(&self, got: &VariableType, expected: &VariableType) -> bool {
+        Self::is_assignable(got, expected) || self.get_dyn_coercion(got, expected).is_some()
+    }
+
+    /// The label of the vtable of the struct for the trait, which is created when first used.
+    fn get_vtable(&mut self, struct_type: &StructType, trait_identifier: &str) -> String {
+        let label = format!("vtable_{}_{trait_identifier}", struct_type.identifier);
+
+        if !self.global_data.contains_key(&label) {
+            let methods = &self.structs[struct_type.id].methods;
+            let functions = self.traits[trait_identifier]
+                .methods
+                .iter()
+                .map(|method| OperandValue::FunctionLocation(methods[&method.identifier]))
+                .collect();
+
+            self.global_data
+                .insert(label.clone(), GlobalData::ReadOnly(functions));
+        }
+
+        label
+    }
+
+    /// Pushes the expression as a value of the expected type,
+    /// adding the vtable when a pointer is turned into a `&dyn Trait`.
+    pub fn handle_coerced_expression(
+        &mut self,
+        expression: &Expression,
+        expected: &VariableType,
+    ) -> Result<Builder, CompilerError> {
+        let got = self.infer_type(expression)?;
+        let builder = self.handle_expression(expression)?;
+
+        Ok(match self.get_dyn_coercion(&got, expected) {
+            Some((struct_type, trait_identifier)) => {
+                let vtable = self.get_vtable(&struct_type, &trait_identifier);
+
+                builder.push(Procedure::new(
+                    expression.pos.clone(),
+                    ProcedureKind::Push(OperandValue::DataPointerLocation(vtable)),
+                ))
+            }
+            None => builder,
+        })
+    }
+
+    /// The static value of the expression as the expected type, see `handle_coerced_expression`.
+    pub fn get_static_coerced_data(
+        &mut self,
+        expression: &Expression,
+        expected: &VariableType,
+    ) -> Result<Vec<OperandValue>, CompilerError> {
+        let got = self.infer_type(expression)?;
+        let mut data = self.get_static_data(expression)?;
+
+        if let Some((struct_type, trait_identifier)) = self.get_dyn_coercion(&got, expected) {
+            let vtable = self.get_vtable(&struct_type, &trait_identifier);
+            data.push(OperandValue::DataPointerLocation(vtable));
+        }
+
+        Ok(data)
+    }
+
+    fn is_self(typ: &Type) -> bool {
+        matches!(&typ.identifier, TypeIdentifier::Struct(identifier) if identifier == "Self")
+    }
+
+    /// Whether the type refers to `Self`.
+    fn refers_to_self(typ: &Type) -> bool {
+        let returns_self = match &typ.identifier {
+            TypeIdentifier::Function(Some(return_type)) => Self::refers_to_self(return_type),
+            _ => false,
+        };
+
+        Self::is_self(typ)
+            || returns_self
+            || typ.children.iter().flatten().any(Self::refers_to_self)
+    }
+
+    /// Only methods taking `self: &Self`, and not using `Self` otherwise,
+    /// can be called without knowing the type of the value.
+    fn is_dyn_compatible(method: &TraitMethod) -> bool {
+        let Some((receiver, parameters)) = method.parameters.split_first() else {
+            return false;
+        };
+
+        let pointee = match (&receiver.typ.identifier, receiver.typ.children.as_deref()) {
+            (TypeIdentifier::Primitive(Keyword::Pointer), Some([pointee])) => {
+                match (&pointee.identifier, pointee.children.as_deref()) {
+                    (TypeIdentifier::Primitive(Keyword::Const), Some([inner])) => Some(inner),
+                    _ => Some(pointee),
+                }
+            }
+            _ => None,
+        };
+
+        receiver.identifier == "self"
+            && pointee.is_some_and(Self::is_self)
+            && !parameters
+                .iter()
+                .any(|parameter| Self::refers_to_self(&parameter.typ))
+            && !method
+                .return_type
+                .as_ref()
+                .is_some_and(Self::refers_to_self)
+    }
+
+    /// The method of the trait called on a `&dyn Trait`.
+    pub fn get_dyn_method(
+        &self,
+        expression: &Expression,
+        access: &MemberAccess,
+        trait_identifier: &str,
+    ) -> Result<DynMethod, CompilerError> {
+        let trait_declaration = &self.traits[trait_identifier];
+
+        let Some((index, method)) = trait_declaration
+            .methods
+            .iter()
+            .enumerate()
+            .find(|(_, method)| method.identifier == access.member)
+        else {
+            return Err(CompilerError::new(
+                expression.pos.clone(),
+                CompilerErrorKind::UndefinedMethod {
+                    typ: VariableType::Dyn(trait_identifier.to_string()),
+                    method: access.member.clone(),
+                },
+            ));
+        };
+
+        if !Self::is_dyn_compatible(method) {
+            return Err(CompilerError::new(
+                expression.pos.clone(),
+                CompilerErrorKind::DynIncompatibleMethod {
+                    trait_identifier: trait_identifier.to_string(),
+                    method: method.identifier.clone(),
+                    method_pos: method.identifier_pos.clone(),
+                },
+            ));
+        }
+
+        let parameters = method.parameters[1..]
+            .iter()
+            .map(|parameter| {
+                Ok((
+                    self.get_generic_type(&parameter.typ, &HashMap::new())?,
+                    self.get_type_alias(&parameter.typ),
+                    parameter.pos.clone(),
+                ))
+            })
+            .collect::<Result<_, CompilerError>>()?;
+
+        let return_type = method
+            .return_type
+            .as_ref()
+            .map(|typ| self.get_generic_type(typ, &HashMap::new()))
+            .transpose()?;
+
+        Ok(DynMethod {
+            index,
+            parameters,
+            return_type,
+        })
+    }
+
+    /// Stores the `&dyn Trait` in a temporary, so that it is evaluated once,
+    /// and pushes the pointer to the value, which is passed as `self`.
+    pub fn handle_dyn_receiver(
+        &mut self,
+        receiver: &Expression,
+        location: &VariableLocation,
+    ) -> Result<Builder, CompilerError> {
+        let typ = self.infer_type(receiver)?;
+        let pos = &receiver.pos;
+
+        Ok(Self::get_push_procedures(&typ, location, true)
+            .into_iter()
+            .fold(
+                self.handle_expression(receiver)?.push(Procedure::new(
+                    pos.clone(),
+                    ProcedureKind::Assign(Assign {
+                        location: location.clone(),
+                        size: Self::get_type_size(&typ),
+                    }),
+                )),
+                |builder, kind| builder.push(Procedure::new(pos.clone(), kind)),
+            )
+            .push(Procedure::new(pos.clone(), ProcedureKind::Deref)))
+    }
+
+    /// Pushes the address of the method, read from the vtable of the stored `&dyn Trait`.
+    pub fn handle_dyn_function(
+        &self,
+        receiver: &Expression,
+        location: &VariableLocation,
+        index: usize,
+    ) -> Result<Builder, CompilerError> {
+        let typ = self.infer_type(receiver)?;
+        let pos = &receiver.pos;
+
+        Ok(Self::get_push_procedures(&typ, location, true)
+            .into_iter()
+            .fold(Builder::new(), |builder, kind| {
+                builder.push(Procedure::new(pos.clone(), kind))
+            })
+            .push(Procedure::new(
+                pos.clone(),
+                ProcedureKind::Push(OperandValue::Int(8)),
+            ))
+            .push(Procedure::new(
+                pos.clone(),
+                ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+            ))
+            .push(Procedure::new(pos.clone(), ProcedureKind::Deref))
+            .push(Procedure::new(
+                pos.clone(),
+                ProcedureKind::Push(OperandValue::Int(index as i32 * 8)),
+            ))
+            .push(Procedure::new(
+                pos.clone(),
+                ProcedureKind::Arithmetic(Arithmetic::Add, RegisterSize::B64),
+            ))
+            .push(Procedure::new(pos.clone(), ProcedureKind::Deref)))
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::{
     lexer::Keyword,
     parser::definition::{Statement, WhileStatement},
@@ -22,8 +24,52 @@ impl Program {
             _ => todo!("Wrong type for while loop"),
         };
 
-        let condition = self.handle_expression(&while_statement.condition)?;
-        let content = self.with_scope(|this| this.get_procedures(&while_statement.content))?;
+        // The loop might not run at all, so assignments
+        // inside of it don't count after the loop.
+        let unassigned = self.get_unassigned();
+
+        // Variables assigned in one iteration are already assigned in the next,
+        // so the loop is compiled again with them until no more are found.
+        let mut assigned = self.get_assigned();
+
+        // Pointers known to not be null when entering the loop have to still be
+        // at the end of it, otherwise the loop is compiled again without them.
+        let mut non_null = self.get_non_null();
+
+        let (condition, content) = loop {
+            self.set_non_null(non_null.clone());
+            self.set_unassigned(unassigned.clone());
+            self.set_assigned(assigned.clone());
+
+            let condition = self.handle_expression(&while_statement.condition)?;
+
+            if let Some((loc, false)) = self.get_null_comparison(&while_statement.condition) {
+                let mut inside = non_null.clone();
+                inside.insert(loc);
+                self.set_non_null(inside);
+            }
+
+            let content = self.with_scope(|this| this.get_procedures(&while_statement.content))?;
+
+            // Variables declared inside the loop get new locations every time it is compiled
+            let end = self.get_non_null();
+            let end_assigned: HashSet<isize> = self
+                .get_assigned()
+                .intersection(&unassigned)
+                .copied()
+                .collect();
+
+            if non_null.iter().all(|loc| end.contains(loc)) && end_assigned.is_subset(&assigned) {
+                break (condition, content);
+            }
+
+            non_null.retain(|loc| end.contains(loc));
+            assigned.extend(end_assigned);
+        };
+
+        self.set_unassigned(unassigned);
+        self.set_assigned(assigned);
+        self.set_non_null(non_null);
 
         let builder = Builder::new().push(Procedure::new(
             statement.pos.clone(),
@@ -44,6 +44,21 @@ pub struct RostError {
     margin: usize,
 }
 
+/// Zero indexed line and column of the character at `pos`.
+pub fn get_line_position(text: &str, pos: usize) -> (usize, usize) {
+    let newlines = text
+        .chars()
+        .enumerate()
+        .take(pos)
+        .filter(|&(_, v)| v == '\n')
+        .map(|(i, _)| i);
+
+    let line = newlines.clone().count();
+    let line_pos = newlines.last().map(|i| pos - i - 1).unwrap_or(pos);
+
+    (line, line_pos)
+}
+
 impl RostError {
     pub fn new(kind: String, elements: Vec<RostErrorElement>) -> Self {
         Self {
@@ -81,20 +96,7 @@ impl RostError {
             let message = &element.message;
 
             let width = pos.end - pos.start;
-
-            let newlines = text
-                .chars()
-                .enumerate()
-                .take(pos.start)
-                .filter(|&(_, v)| v == '\n')
-                .map(|(i, _)| i);
-
-            let line = newlines.clone().count();
-            let line_pos = newlines
-                .clone()
-                .last()
-                .map(|i| pos.start - i - 1)
-                .unwrap_or(pos.start);
+            let (line, line_pos) = get_line_position(text, pos.start);
 
             (line, line_pos, width, message)
         })
@@ -10,6 +10,21 @@ pub enum Keyword {
     If,
     Else,
     While,
+    Type,
+    As,
+    SizeOf,
+    AlignOf,
+    OffsetOf,
+    Mut,
+    Const,
+    Trait,
+    Impl,
+    For,
+    Dyn,
+    Null,
+    Defer,
+    Extern,
+    Export,
     Plus,
     Minus,
     Asterix,
@@ -27,7 +42,11 @@ pub enum Keyword {
     LessThan,
     GreaterThan,
     Equality,
+    NotEqual,
+    QuestionMark,
     Dot,
+    DotDot,
+    Ellipsis,
 
     // Types
     Int,
@@ -49,6 +68,7 @@ pub enum Literal {
     String(String),
     Int(i32),
     Bool(bool),
+    Null,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -14,6 +14,21 @@ static KEYWORDS: phf::Map<&'static str, Keyword> = phf_map! {
     "else" => Keyword::Else,
     "while" => Keyword::While,
     "struct" => Keyword::Struct,
+    "type" => Keyword::Type,
+    "as" => Keyword::As,
+    "sizeof" => Keyword::SizeOf,
+    "alignof" => Keyword::AlignOf,
+    "offsetof" => Keyword::OffsetOf,
+    "mut" => Keyword::Mut,
+    "const" => Keyword::Const,
+    "trait" => Keyword::Trait,
+    "impl" => Keyword::Impl,
+    "for" => Keyword::For,
+    "dyn" => Keyword::Dyn,
+    "null" => Keyword::Null,
+    "defer" => Keyword::Defer,
+    "extern" => Keyword::Extern,
+    "export" => Keyword::Export,
 };
 
 pub struct KeywordLexer {
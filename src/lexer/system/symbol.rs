@@ -22,7 +22,11 @@ static SYMBOLS: phf::Map<&'static str, Keyword> = phf_map! {
     "<" => Keyword::LessThan,
     ">" => Keyword::GreaterThan,
     "==" => Keyword::Equality,
+    "!=" => Keyword::NotEqual,
+    "?" => Keyword::QuestionMark,
     "." => Keyword::Dot,
+    ".." => Keyword::DotDot,
+    "..." => Keyword::Ellipsis,
 };
 
 pub struct SymbolLexer;
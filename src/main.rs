@@ -74,11 +74,13 @@ fn shell(settings: Settings) {
                     continue;
                 }
 
-                let compiled = parsed.and_then(|parsed| match compiler::compile(parsed) {
-                    Ok(code) => Some(code),
-                    Err(err) => {
-                        print_error(err.into());
-                        None
+                let compiled = parsed.and_then(|parsed| {
+                    match compiler::compile(parsed, settings.checked, settings.library) {
+                        Ok(code) => Some(code),
+                        Err(err) => {
+                            print_error(err.into());
+                            None
+                        }
                     }
                 });
 
@@ -88,7 +90,12 @@ fn shell(settings: Settings) {
                 }
 
                 let nasm = compiled.and_then(|compiled| {
-                    match nasm::generate(&compiled, !settings.remove_comments, settings.optimize) {
+                    match nasm::generate(
+                        &compiled,
+                        !settings.remove_comments,
+                        settings.optimize,
+                        ("?", &buf),
+                    ) {
                         Ok(code) => Some(code),
                         Err(err) => {
                             print_error(err.into());
@@ -151,11 +158,13 @@ fn run(settings: Settings) -> Option<Code> {
         return None;
     }
 
-    let compiled = parsed.and_then(|parsed| match compiler::compile(parsed) {
-        Ok(code) => Some(code),
-        Err(err) => {
-            print_error(err.into());
-            None
+    let compiled = parsed.and_then(|parsed| {
+        match compiler::compile(parsed, settings.checked, settings.library) {
+            Ok(code) => Some(code),
+            Err(err) => {
+                print_error(err.into());
+                None
+            }
         }
     });
 
@@ -165,7 +174,12 @@ fn run(settings: Settings) -> Option<Code> {
     }
 
     let nasm = compiled.and_then(|compiled| {
-        match nasm::generate(&compiled, !settings.remove_comments, settings.optimize) {
+        match nasm::generate(
+            &compiled,
+            !settings.remove_comments,
+            settings.optimize,
+            (&file, text),
+        ) {
             Ok(code) => Some(code),
             Err(err) => {
                 print_error(err.into());
@@ -184,6 +198,8 @@ fn run(settings: Settings) -> Option<Code> {
 
 struct Settings {
     pub optimize: bool,
+    pub checked: bool,
+    pub library: bool,
     pub remove_comments: bool,
     pub lsp: bool,
     pub file: Option<String>,
@@ -195,6 +211,8 @@ impl Default for Settings {
     fn default() -> Self {
         Self {
             optimize: true,
+            checked: false,
+            library: false,
             remove_comments: false,
             file: None,
             shell_level: ShellLevel::End,
@@ -216,6 +234,8 @@ fn main() -> std::io::Result<()> {
             "-lsp" => settings.lsp = true,
             "-no-comments" => settings.remove_comments = true,
             "-no-optimize" => settings.optimize = false,
+            "-checked" => settings.checked = true,
+            "-lib" => settings.library = true,
             "-s" => settings.run_shell = true,
             "-sl" => {
                 if let Some(level) = &args
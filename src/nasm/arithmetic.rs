@@ -11,6 +11,7 @@ impl<'a> Generator<'a> {
         let label = Self::get_procedure_name(procedure, Some("equality"));
         let jump = match arithmetic {
             Arithmetic::Equality => Row::JumpIfEquals(label.clone()),
+            Arithmetic::NotEqual => Row::JumpIfNotEquals(label.clone()),
             Arithmetic::LessThan => Row::JumpIfLessThan(label.clone()),
             Arithmetic::GreaterThan => Row::JumpIfGreaterThan(label.clone()),
             _ => unreachable!(),
@@ -40,9 +41,10 @@ impl<'a> Generator<'a> {
                 Row::Xor("rdx".into(), "rdx".into()),
                 Row::Divide("rbx".into()),
             ],
-            Arithmetic::Equality | Arithmetic::LessThan | Arithmetic::GreaterThan => {
-                Self::get_equality_operations(procedure, arithmetic, size)
-            }
+            Arithmetic::Equality
+            | Arithmetic::NotEqual
+            | Arithmetic::LessThan
+            | Arithmetic::GreaterThan => Self::get_equality_operations(procedure, arithmetic, size),
             #[allow(unreachable_patterns)]
             _ => panic!("Unimplemented: {arithmetic:?}"),
         };
@@ -1,25 +1,73 @@
-use crate::compiler::{definition::Assign, scope::variable::VariableLocation};
+use crate::compiler::{
+    definition::{Assign, RegisterSize},
+    scope::variable::VariableLocation,
+};
 
 use super::{error::NasmError, generator::Generator, row::Row};
 
 impl<'a> Generator<'a> {
     pub fn handle_assign(&mut self, assign: &Assign) -> Result<(), NasmError> {
-        for i in 0..assign.size / 8 {
+        let slots = assign.size.div_ceil(8);
+
+        // The address is pushed before the value, and points to its lowest slot
+        if let VariableLocation::Address = assign.location {
+            self.code
+                .add(Row::Move("rbx".into(), format!("[rsp+{}]", slots * 8)));
+        }
+
+        for i in 0..slots {
             self.code.add(Row::Pop("rax".into()));
 
-            let into = match &assign.location {
-                VariableLocation::Stack(loc) => self.get_absolute_stack_location(*loc + i as isize),
-                VariableLocation::Global(label) => format!("[{label}]"),
-                VariableLocation::Address => {
-                    self.code.add(Row::Pop("rbx".into()));
+            // The highest slot is popped first, and might only be partly used
+            let bytes = match i {
+                0 => assign.size - (slots - 1) * 8,
+                _ => 8,
+            };
 
-                    "[rbx]".into()
+            match &assign.location {
+                // Only write the bytes of the value, a neighbouring value might share the slot
+                VariableLocation::Address if bytes < 8 => {
+                    self.add_partial_write((slots - 1 - i) * 8, bytes)
                 }
-            };
+                VariableLocation::Address => {
+                    self.code.add(Row::Move(
+                        format!("[rbx+{}]", (slots - 1 - i) * 8),
+                        "rax".into(),
+                    ));
+                }
+                VariableLocation::Stack(loc) => {
+                    let into = self.get_absolute_stack_location(*loc + i as isize);
+                    self.code.add(Row::Move(into, "rax".into()));
+                }
+                VariableLocation::Global(label) => {
+                    self.code
+                        .add(Row::Move(format!("[{label}-{}]", i * 8), "rax".into()));
+                }
+            }
+        }
 
-            self.code.add(Row::Move(into, "rax".into()));
+        if let VariableLocation::Address = assign.location {
+            self.code.add(Row::Pop("rbx".into()));
         }
 
         Ok(())
     }
+
+    /// Writes the lowest `bytes` bytes of `rax` to `offset` bytes after the address in `rbx`.
+    fn add_partial_write(&mut self, offset: usize, bytes: usize) {
+        if matches!(bytes, 1 | 2 | 4) {
+            let from = RegisterSize::get_register(bytes).get_register_name("a");
+            self.code.add(Row::Move(format!("[rbx+{offset}]"), from));
+            return;
+        }
+
+        for byte in 0..bytes {
+            if byte > 0 {
+                self.code.add(Row::ShiftRight("rax".into(), "8".into()));
+            }
+
+            self.code
+                .add(Row::Move(format!("[rbx+{}]", offset + byte), "al".into()));
+        }
+    }
 }
@@ -0,0 +1,106 @@
+use crate::{compiler::definition::Procedure, error::get_line_position};
+
+use super::{code::Code, error::NasmError, generator::Generator, row::Row};
+
+static BOUNDS_CHECK_FAILURE: &str = "_bounds_check_failure";
+
+impl<'a> Generator<'a> {
+    pub fn get_bounds_message_name(index: usize) -> String {
+        format!("_bounds_message_{index}")
+    }
+
+    /// Pops the length and compares it to the index below it.
+    /// The comparison is unsigned, so negative indices are out of bounds as well.
+    pub fn handle_bounds_check(
+        &mut self,
+        procedure_label: &str,
+        procedure: &Procedure,
+    ) -> Result<(), NasmError> {
+        let (file, code) = self.source;
+        let (line, column) = get_line_position(code, procedure.pos.start);
+
+        let message = Self::get_bounds_message_name(self.bounds_messages.len());
+        self.bounds_messages.push(format!(
+            "{file}:{}:{}: index %ld is out of bounds for length %ld\n",
+            line + 1,
+            column + 1
+        ));
+
+        let in_bounds = Self::get_procedure_name(procedure_label, Some("in_bounds"));
+
+        self.code
+            .add(Row::Pop("rbx".into()))
+            .add(Row::Move("rax".into(), "[rsp]".into()))
+            .add(Row::Compare("rax".into(), "rbx".into()))
+            .add(Row::JumpIfBelow(in_bounds.clone()))
+            .add(Row::LoadEffectiveAddress(
+                "rsi".into(),
+                format!("[{message}]"),
+            ))
+            .add(Row::Move("rdx".into(), "rax".into()))
+            .add(Row::Move("rcx".into(), "rbx".into()))
+            .add(Row::Call(BOUNDS_CHECK_FAILURE.into()))
+            .add(Row::Label(in_bounds));
+
+        Ok(())
+    }
+
+    /// Exits if the pointer on top of the stack is null, leaving it on the stack.
+    pub fn handle_null_check(
+        &mut self,
+        procedure_label: &str,
+        procedure: &Procedure,
+    ) -> Result<(), NasmError> {
+        let (file, code) = self.source;
+        let (line, column) = get_line_position(code, procedure.pos.start);
+
+        let message = Self::get_bounds_message_name(self.bounds_messages.len());
+        self.bounds_messages.push(format!(
+            "{file}:{}:{}: null pointer dereference\n",
+            line + 1,
+            column + 1
+        ));
+
+        let not_null = Self::get_procedure_name(procedure_label, Some("not_null"));
+
+        self.code
+            .add(Row::Move("rax".into(), "[rsp]".into()))
+            .add(Row::Compare("rax".into(), "0".into()))
+            .add(Row::JumpIfNotEquals(not_null.clone()))
+            .add(Row::LoadEffectiveAddress(
+                "rsi".into(),
+                format!("[{message}]"),
+            ))
+            .add(Row::Call(BOUNDS_CHECK_FAILURE.into()))
+            .add(Row::Label(not_null));
+
+        Ok(())
+    }
+
+    /// Prints the message in `rsi`, formatted with the index in `rdx`
+    /// and the length in `rcx`, to stderr and exits the program.
+    /// Output buffered by earlier calls to printf is flushed first, to keep the order.
+    pub fn add_bounds_check_failure(&mut self) -> &mut Code {
+        if self.bounds_messages.is_empty() {
+            return &mut self.code;
+        }
+
+        self.code
+            .add(Row::Comment("[bounds check failure]".into()))
+            .add(Row::Label(BOUNDS_CHECK_FAILURE.into()))
+            .add(Row::And("rsp".into(), "-16".into()))
+            .add(Row::Move("r12".into(), "rsi".into()))
+            .add(Row::Move("r13".into(), "rdx".into()))
+            .add(Row::Move("r14".into(), "rcx".into()))
+            .add(Row::Xor("rdi".into(), "rdi".into()))
+            .add(Row::Call(Self::get_extern_call("fflush")))
+            .add(Row::Move("rsi".into(), "r12".into()))
+            .add(Row::Move("rdx".into(), "r13".into()))
+            .add(Row::Move("rcx".into(), "r14".into()))
+            .add(Row::Move("rdi".into(), "2".into()))
+            .add(Row::Xor("rax".into(), "rax".into()))
+            .add(Row::Call(Self::get_extern_call("dprintf")))
+            .add(Row::Move("rdi".into(), "1".into()))
+            .add(Row::Call(Self::get_extern_call("exit")))
+    }
+}
@@ -137,23 +137,6 @@ impl Code {
         self
     }
 
-    /// Aligns the stack such that RSP % 16 == 8.
-    /// This is done by pushing and removing a temporary dummy stack element if needed.
-    pub fn aligned<F>(&mut self, inner: F) -> &mut Self
-    where
-        F: FnOnce(&mut Self) -> &mut Self,
-    {
-        self.add(Row::Comment("Aligning stack to 16".into()))
-            .add(Row::Move("rax".into(), "rsp".into()))
-            .add(Row::And("rsp".into(), "-16".into()))
-            .add(Row::Subtract("rsp".into(), "8".into()))
-            .add(Row::Push("rax".into()));
-
-        inner(self);
-
-        self.add(Row::Pop("rsp".into()))
-    }
-
     pub fn add_with_comment(&mut self, row: Row, comment: String) -> &mut Self {
         let stack_delta = row.stack_delta();
         self.rows.push(CodeRow::new(row, false));
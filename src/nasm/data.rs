@@ -1,18 +1,46 @@
-use crate::compiler::definition::GlobalData;
+use crate::compiler::definition::{GlobalData, OperandValue};
 
 use super::{code::Code, generator::Generator, row::Row};
 
 impl<'a> Generator<'a> {
+    fn get_data_value(operand: &OperandValue) -> String {
+        match operand {
+            OperandValue::Int(i) => format!("{i}"),
+            OperandValue::DataPointerLocation(label) => label.clone(),
+            OperandValue::FunctionLocation(function_id) => Self::get_function_name(*function_id),
+            _ => unreachable!("Value cannot be static: {operand:?}"),
+        }
+    }
+
     fn add_data_section(&mut self) {
         self.code.add(Row::Section("data".into()));
 
+        for (i, message) in self.bounds_messages.iter().enumerate() {
+            self.code.add(Row::Label(Self::get_bounds_message_name(i)));
+            self.code.add(Row::DeclareStaticString(message.clone()));
+        }
+
         for (label, data) in self.program.global_data.iter() {
             match data {
                 GlobalData::String(s) => {
                     self.code.add(Row::Label(label.clone()));
                     self.code.add(Row::DeclareStaticString(s.clone()));
                 }
-                GlobalData::Reserved(_) => {}
+                GlobalData::Initialized(values) => {
+                    // Globals grow downwards from their label, the same way
+                    // as variables on the stack, so that the first pushed
+                    // value ends up at the lowest address.
+                    let values = values.iter().map(Self::get_data_value).collect::<Vec<_>>();
+                    let (last, rest) = values.split_last().unwrap();
+
+                    if !rest.is_empty() {
+                        self.code.add(Row::DeclareQuadWords(rest.to_vec()));
+                    }
+
+                    self.code.add(Row::Label(label.clone()));
+                    self.code.add(Row::DeclareQuadWords(vec![last.clone()]));
+                }
+                GlobalData::Reserved(_) | GlobalData::ReadOnly(_) => {}
             };
         }
     }
@@ -23,18 +51,50 @@ impl<'a> Generator<'a> {
         for (label, data) in self.program.global_data.iter() {
             match data {
                 GlobalData::Reserved(i) => {
+                    // See `add_data_section` for the layout
+                    if *i > 1 {
+                        self.code.add(Row::ReserveBytes((i - 1) * 8));
+                    }
+
                     self.code.add(Row::Label(label.clone()));
-                    self.code.add(Row::ReserveBytes(i * 8));
+                    self.code.add(Row::ReserveBytes(8));
                 }
-                GlobalData::String(_) => {}
+                GlobalData::String(_) | GlobalData::Initialized(_) | GlobalData::ReadOnly(_) => {}
             };
         }
     }
 
+    /// Vtables, which are indexed upwards from their label unlike globals.
+    /// The function addresses are relocated when a shared object is loaded,
+    /// so they are only read-only afterwards.
+    fn add_rodata_section(&mut self) {
+        self.code.add(Row::Section(
+            "data.rel.ro progbits alloc noexec write align=8".into(),
+        ));
+
+        for (label, data) in self.program.global_data.iter() {
+            if let GlobalData::ReadOnly(values) = data {
+                // Vtables of traits without methods still need an address
+                let values = if values.is_empty() {
+                    vec!["0".to_string()]
+                } else {
+                    values.iter().map(Self::get_data_value).collect()
+                };
+
+                self.code.add(Row::Label(label.clone()));
+                self.code.add(Row::DeclareQuadWords(values));
+            }
+        }
+    }
+
     pub fn add_data(&mut self) -> &mut Code {
         self.add_bss_section();
         self.add_data_section();
+        self.add_rodata_section();
 
-        &mut self.code
+        // Without this note the linker makes the stack executable
+        self.code.add(Row::Section(
+            "note.GNU-stack noalloc noexec nowrite progbits".into(),
+        ))
     }
 }
@@ -3,9 +3,7 @@ use std::ops::Range;
 use crate::error::{RostError, RostErrorElement};
 
 #[derive(Debug, PartialEq)]
-pub enum NasmErrorKind {
-    TooManyArguments(usize),
-}
+pub enum NasmErrorKind {}
 
 #[derive(Debug, PartialEq)]
 pub struct NasmError {
@@ -14,17 +12,8 @@ pub struct NasmError {
 }
 
 impl NasmError {
-    pub fn new(pos: Range<usize>, kind: NasmErrorKind) -> Self {
-        Self { pos, kind }
-    }
-
     fn get_messages(&self) -> Vec<(String, Range<usize>)> {
-        match self.kind {
-            NasmErrorKind::TooManyArguments(a) => vec![(
-                format!("Too many arguments ({a}) to function (no more than 6 supported)"),
-                self.pos.clone(),
-            )],
-        }
+        match self.kind {}
     }
 }
 
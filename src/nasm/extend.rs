@@ -0,0 +1,25 @@
+use crate::compiler::definition::{Extend, RegisterSize};
+
+use super::{error::NasmError, generator::Generator, row::Row};
+
+impl<'a> Generator<'a> {
+    pub fn handle_extend(&mut self, extend: &Extend) -> Result<(), NasmError> {
+        let from = extend.size.get_register_name("a");
+
+        let row = match (&extend.size, extend.signed) {
+            (RegisterSize::B64, _) => return Ok(()),
+            // Writing to a 32 bit register clears the upper half
+            (RegisterSize::B32, false) => Row::Move("EAX".into(), from),
+            (RegisterSize::B32, true) => Row::MoveSignExtendDoubleWord("RAX".into(), from),
+            (_, false) => Row::MoveZeroExtend("RAX".into(), from),
+            (_, true) => Row::MoveSignExtend("RAX".into(), from),
+        };
+
+        self.code
+            .add(Row::Pop("rax".into()))
+            .add(row)
+            .add(Row::Push("rax".into()));
+
+        Ok(())
+    }
+}
@@ -1,40 +1,122 @@
-use std::ops::Range;
-
-use crate::compiler::definition::{Procedure, ProcedureCall, SystemCall};
-
-use super::{
-    error::{NasmError, NasmErrorKind},
-    generator::Generator,
-    row::Row,
+use crate::compiler::{
+    definition::{ArgumentLocation, PointerCall, Procedure, ProcedureCall, SystemCall},
+    program::Program,
 };
 
-static ARG_REG: &[&str] = &["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+use super::{error::NasmError, generator::Generator, row::Row};
+
+pub static ARG_REG: &[&str] = &["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
 
 impl<'a> Generator<'a> {
-    fn push_args(&mut self, nargs: usize, pos: Range<usize>) -> Result<(), NasmError> {
-        if nargs > ARG_REG.len() {
-            return Err(NasmError::new(pos, NasmErrorKind::TooManyArguments(nargs)));
+    /// C functions are called through the procedure linkage table,
+    /// as they may be in a shared object loaded at any address.
+    pub fn get_extern_call(identifier: &str) -> String {
+        format!("{identifier} wrt ..plt")
+    }
+
+    /// Keeps the stack aligned to 16 bytes at calls, when `slots` are pushed after the padding.
+    /// The frame is aligned after the base pointer is saved,
+    /// and the memory allocated for variables keeps the alignment.
+    fn add_alignment_padding(&mut self, slots: usize) -> usize {
+        let padding = (self.code.stack_pos - 1 + slots) % 2;
+
+        if padding > 0 {
+            self.code
+                .add(Row::Subtract("rsp".into(), format!("{}", padding * 8)));
+            self.code.stack_pos += padding;
+        }
+
+        padding
+    }
+
+    /// Calls the target with the arguments on top of the stack, the first one pushed first,
+    /// following the System V calling convention. The arguments are removed afterwards.
+    ///
+    /// Values of several slots are pushed in the opposite order of how they are stored,
+    /// so arguments are copied slot by slot into registers and the stack.
+    fn add_call(&mut self, args: &[usize], target: String) {
+        let locations = Program::get_argument_locations(args);
+        let total: usize = args.iter().sum();
+
+        // Slots between the top of the stack and the first 8 bytes of each argument
+        let mut below = total;
+        let offsets = args
+            .iter()
+            .map(|slots| {
+                below -= slots;
+                below + slots - 1
+            })
+            .collect::<Vec<_>>();
+
+        let stack_args = locations
+            .iter()
+            .zip(args)
+            .filter(|(location, _)| **location == ArgumentLocation::Stack)
+            .map(|(_, slots)| slots)
+            .sum();
+        let mut added = self.add_alignment_padding(stack_args);
+
+        // The last stack argument is pushed first, its last 8 bytes at the highest address
+        for ((location, slots), offset) in locations.iter().zip(args).zip(&offsets).rev() {
+            if *location != ArgumentLocation::Stack {
+                continue;
+            }
+
+            for slot in (0..*slots).rev() {
+                self.code.add(Row::Push(format!(
+                    "qword [rsp+{}]",
+                    (offset - slot + added) * 8
+                )));
+                added += 1;
+            }
         }
 
-        for i in 0..nargs {
-            let reg = ARG_REG[nargs - i - 1];
-            self.code.add(Row::Pop(reg.into()));
+        for ((location, slots), offset) in locations.iter().zip(args).zip(&offsets) {
+            let ArgumentLocation::Registers(register) = location else {
+                continue;
+            };
+
+            for slot in 0..*slots {
+                self.code.add(Row::Move(
+                    ARG_REG[register + slot].into(),
+                    format!("[rsp+{}]", (offset - slot + added) * 8),
+                ));
+            }
         }
 
-        Ok(())
+        // `al` holds the amount of vector registers used by variadic functions, which is none
+        self.code
+            .add(Row::Xor("rax".into(), "rax".into()))
+            .add(Row::Call(target))
+            .add(Row::Add("rsp".into(), format!("{}", (total + added) * 8)));
+
+        self.code.stack_pos -= total + added;
+    }
+
+    /// Pushes the value returned in `rax:rdx`, see `handle_function_return`.
+    fn push_return_value(&mut self, slots: usize) {
+        match slots {
+            0 => {}
+            1 => {
+                self.code.add(Row::Push("rax".into()));
+            }
+            2 => {
+                self.code
+                    .add(Row::Push("rax".into()))
+                    .add(Row::Push("rdx".into()));
+            }
+            _ => todo!("Cannot return {slots} slots"),
+        }
     }
 
     pub fn handle_system_call(
         &mut self,
-        procedure: &Procedure,
+        _procedure: &Procedure,
         call: &SystemCall,
     ) -> Result<(), NasmError> {
-        self.push_args(call.nargs, procedure.pos.clone())?;
+        self.add_call(&call.args, Self::get_extern_call(&call.identifier));
 
-        self.code.aligned(|code| {
-            code.add(Row::Xor("rax".into(), "rax".into()))
-                .add(Row::Call(call.identifier.clone()))
-        });
+        self.push_return_value(call.return_slots);
 
         Ok(())
     }
@@ -44,13 +126,24 @@ impl<'a> Generator<'a> {
         _procedure: &Procedure,
         call: &ProcedureCall,
     ) -> Result<(), NasmError> {
-        self.code
-            .add(Row::Xor("rax".into(), "rax".into())) // Return value in rax, default 0
-            .add(Row::Call(Self::get_function_name(call.function_id)));
+        self.add_call(&call.args, Self::get_function_name(call.function_id));
 
-        if call.returns {
-            self.code.add(Row::Push("rax".into()));
-        }
+        self.push_return_value(call.return_slots);
+
+        Ok(())
+    }
+
+    /// Calls the function whose address is on top of the stack,
+    /// the arguments are placed below it.
+    pub fn handle_pointer_call(
+        &mut self,
+        _procedure: &Procedure,
+        call: &PointerCall,
+    ) -> Result<(), NasmError> {
+        self.code.add(Row::Pop("r10".into()));
+        self.add_call(&call.args, "r10".into());
+
+        self.push_return_value(call.return_slots);
 
         Ok(())
     }
@@ -3,16 +3,19 @@ use crate::compiler::definition::Function;
 use super::{code::Code, error::NasmError, generator::Generator, row::Row};
 
 impl<'a> Generator<'a> {
+    /// `rbx` belongs to the caller, and is saved right below the base pointer.
     pub fn save_base_pointer(&mut self) -> &mut Code {
         self.code
             .add(Row::Comment("Save base pointer".into()))
             .add(Row::Push("rbp".into()))
             .add(Row::Move("rbp".into(), "rsp".into()))
+            .add(Row::Push("rbx".into()))
     }
 
     pub fn restore_base_pointer(&mut self) -> &mut Code {
         self.code
             .add(Row::Comment("Restore base pointer".into()))
+            .add(Row::Move("rbx".into(), "[rbp-8]".into()))
             .add(Row::Move("rsp".into(), "rbp".into()))
             .add(Row::Pop("rbp".into()))
     }
@@ -26,11 +29,20 @@ impl<'a> Generator<'a> {
 
         let old_stack_pos = self.code.stack_pos;
 
+        // The main function of a program is already global, as the entry point
+        match &function.export {
+            Some(export) if self.program.library || export != "main" => {
+                self.code
+                    .add(Row::Global(format!("{export}:function")))
+                    .add(Row::Label(export.clone()));
+            }
+            _ => {}
+        }
+
         self.code.add(Row::Label(name.clone()));
 
+        self.code.stack_pos = 0;
         self.save_base_pointer();
-
-        self.code.stack_pos = 1;
         self.add_program(&function.body, &name)?;
         self.code.stack_pos = old_stack_pos;
 
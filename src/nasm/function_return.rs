@@ -3,11 +3,31 @@ use crate::compiler::definition::Procedure;
 use super::{error::NasmError, generator::Generator, row::Row};
 
 impl<'a> Generator<'a> {
-    pub fn handle_function_return(&mut self, _procedure: &Procedure) -> Result<(), NasmError> {
-        self.code.add(Row::Move("rax".into(), "[rsp]".into()));
+    /// Values of two slots are returned in `rax:rdx`,
+    /// with the first pushed slot in `rax`.
+    pub fn handle_function_return(
+        &mut self,
+        _procedure: &Procedure,
+        slots: usize,
+    ) -> Result<(), NasmError> {
+        let stack_pos = self.code.stack_pos;
+
+        match slots {
+            0 => &mut self.code,
+            1 => self.code.add(Row::Move("rax".into(), "[rsp]".into())),
+            2 => self
+                .code
+                .add(Row::Move("rax".into(), "[rsp+8]".into()))
+                .add(Row::Move("rdx".into(), "[rsp]".into())),
+            _ => todo!("Cannot return {slots} slots"),
+        };
 
         self.restore_base_pointer().add(Row::Ret);
 
+        // The code following the return is only reached by jumping past it,
+        // with the returned value not on the stack.
+        self.code.stack_pos = stack_pos - slots;
+
         Ok(())
     }
 }
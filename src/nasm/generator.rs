@@ -2,11 +2,19 @@ use crate::compiler::{builder::Builder, definition::ProcedureKind, program::Prog
 
 use super::{code::Code, error::NasmError, row::Row};
 
+static INITIALIZER: &str = "_initialize";
+
 pub struct Generator<'a> {
     pub code: Code,
     pub program: &'a Program,
     pub output_comments: bool,
     pub optimize: bool,
+
+    // File name and source code, used to locate failed runtime checks
+    pub source: (&'a str, &'a str),
+
+    // Messages of the bounds checks, in the order they were generated
+    pub bounds_messages: Vec<String>,
 }
 
 impl<'a> Generator<'a> {
@@ -16,6 +24,8 @@ impl<'a> Generator<'a> {
             program,
             output_comments: false,
             optimize: false,
+            source: ("?", ""),
+            bounds_messages: Vec::new(),
         }
     }
 
@@ -29,11 +39,24 @@ impl<'a> Generator<'a> {
         self
     }
 
+    pub fn with_source(mut self, file: &'a str, code: &'a str) -> Self {
+        self.source = (file, code);
+        self
+    }
+
     pub fn generate_code(mut self) -> Result<Code, NasmError> {
         self.add_header();
-        self.add_program(&self.program.procedures, "")?;
-        self.add_exit();
+
+        if self.program.library {
+            self.add_initializer()?;
+        } else {
+            self.add_entry();
+            self.add_program(&self.program.procedures, "")?;
+            self.add_exit();
+        }
+
         self.add_functions()?;
+        self.add_bounds_check_failure();
         self.add_data();
 
         if self.optimize {
@@ -103,8 +126,11 @@ impl<'a> Generator<'a> {
                     self.code.add(Row::Comment(comment.clone()));
                 }
                 ProcedureKind::Allocate(size) => {
-                    self.code
-                        .add(Row::Subtract("rsp".into(), format!("{}", size * 8)));
+                    // An even amount of slots keeps the stack aligned to 16 bytes
+                    self.code.add(Row::Subtract(
+                        "rsp".into(),
+                        format!("{}", size.next_multiple_of(2) * 8),
+                    ));
                 }
                 ProcedureKind::SystemCall(system_call) => {
                     self.handle_system_call(procedure, system_call)?
@@ -112,14 +138,21 @@ impl<'a> Generator<'a> {
                 ProcedureKind::ProcedureCall(procedure_call) => {
                     self.handle_procedure_call(procedure, procedure_call)?
                 }
-                ProcedureKind::Return => self.handle_function_return(procedure)?,
+                ProcedureKind::PointerCall(pointer_call) => {
+                    self.handle_pointer_call(procedure, pointer_call)?
+                }
+                ProcedureKind::Return(slots) => self.handle_function_return(procedure, *slots)?,
                 ProcedureKind::Assign(assign) => self.handle_assign(assign)?,
                 ProcedureKind::Push(operand) => self.handle_push(operand, false)?,
                 ProcedureKind::PushAddress(operand) => self.handle_push(operand, true)?,
                 ProcedureKind::Deref => self.handle_deref()?,
+                ProcedureKind::DerefSlots(slots) => self.handle_deref_slots(*slots)?,
                 ProcedureKind::Arithmetic(arithmetic, size) => {
                     self.handle_arithmetic(&label, arithmetic, size)?
                 }
+                ProcedureKind::Extend(extend) => self.handle_extend(extend)?,
+                ProcedureKind::BoundsCheck => self.handle_bounds_check(&label, procedure)?,
+                ProcedureKind::NullCheck => self.handle_null_check(&label, procedure)?,
                 ProcedureKind::If(ifs) => self.handle_if_statement(&label, ifs)?,
                 ProcedureKind::While(while_statement) => {
                     self.handle_while_statement(&label, while_statement)?
@@ -142,23 +175,52 @@ impl<'a> Generator<'a> {
         format!("[rbp{}{}]", if loc < 0 { '+' } else { '-' }, loc.abs() * 8)
     }
 
+    /// Memory operands with labels are relative to the instruction,
+    /// so the code works at any address, like in shared objects.
     fn add_header(&mut self) -> &mut Code {
         let mut code = self
             .code
             .add(Row::Comment("[header]".into()))
+            .add(Row::Default("rel".into()));
+
+        // Built-in functions, those used by the runtime checks, and those declared `extern`
+        let mut extern_functions = vec!["printf", "exit"];
+
+        if self.program.checked {
+            extern_functions.extend(["fflush", "dprintf"]);
+        }
+
+        let mut declared = self.program.extern_functions.keys().collect::<Vec<_>>();
+        declared.sort();
+
+        for identifier in declared {
+            if !extern_functions.contains(&identifier.as_str()) {
+                extern_functions.push(identifier);
+            }
+        }
+
+        for identifier in extern_functions {
+            code = code.add(Row::Extern(identifier.into()));
+        }
+
+        code.add(Row::Section("text".into()))
+    }
+
+    /// The program starts by running the code outside of functions,
+    /// and then calls the main function.
+    fn add_entry(&mut self) -> &mut Code {
+        self.code
             .add(Row::Global("main".into()))
-            .add(Row::Extern("printf".into()))
-            .add(Row::Section("text".into()))
-            .add(Row::Label("main".into()))
-            .add(Row::Comment("Save base pointer".into()))
-            .add(Row::Push("rbp".into()))
-            .add(Row::Move("rbp".into(), "rsp".into()));
-
-        if self.program.main_func_nparams >= 1 {
+            .add(Row::Label("main".into()));
+
+        let nparams = self.program.main_func_nparams;
+        let mut code = self.save_base_pointer();
+
+        if nparams >= 1 {
             code = code.add(Row::Push("rdi".into())); // argc
         }
 
-        if self.program.main_func_nparams >= 2 {
+        if nparams >= 2 {
             code = code.add(Row::Push("rsi".into())); // argv
         }
 
@@ -168,10 +230,38 @@ impl<'a> Generator<'a> {
     fn add_exit(&mut self) -> &mut Code {
         self.code
             .add(Row::Comment("[exit program]".into()))
-            .add(Row::Comment("Restore base pointer".into()))
-            .add(Row::Move("rsp".into(), "rbp".into()))
-            .add(Row::Pop("rbp".into()))
+            .add(Row::Pop("rax".into())); // Exit status, returned by main
+
+        self.restore_base_pointer().add(Row::Ret)
+    }
+
+    /// Libraries run the code outside of functions when they are loaded,
+    /// by listing it in the `.init_array` section.
+    fn add_initializer(&mut self) -> Result<&mut Code, NasmError> {
+        let procedures = &self.program.procedures;
+        let is_empty = procedures
+            .iter()
+            .all(|procedure| matches!(procedure.kind, ProcedureKind::Comment(_)));
+
+        if is_empty {
+            return Ok(&mut self.code);
+        }
+
+        self.code
+            .add(Row::Comment("[initializer]".into()))
+            .add(Row::Label(INITIALIZER.into()));
+
+        self.code.stack_pos = 0;
+        self.save_base_pointer();
+        self.add_program(procedures, "")?;
+
+        self.restore_base_pointer()
             .add(Row::Ret)
+            .add(Row::Section("init_array alloc write align=8".into()))
+            .add(Row::DeclareQuadWords(vec![INITIALIZER.into()]))
+            .add(Row::Section("text".into()));
+
+        Ok(&mut self.code)
     }
 
     fn add_functions(&mut self) -> Result<&mut Code, NasmError> {
@@ -6,8 +6,10 @@ pub mod code;
 
 mod arithmetic;
 mod assign;
+mod bounds_check;
 mod data;
 mod error;
+mod extend;
 mod function_call;
 mod function_declaration;
 mod function_return;
@@ -21,9 +23,11 @@ pub fn generate(
     program: &Program,
     with_comments: bool,
     with_optimization: bool,
+    source: (&str, &str),
 ) -> Result<Code, NasmError> {
     Generator::new(program)
         .with_comments(with_comments)
         .with_optimization(with_optimization)
+        .with_source(source.0, source.1)
         .generate_code()
 }
@@ -1,6 +1,6 @@
 use crate::compiler::definition::OperandValue;
 
-use super::{error::NasmError, generator::Generator, row::Row};
+use super::{error::NasmError, function_call::ARG_REG, generator::Generator, row::Row};
 
 impl<'a> Generator<'a> {
     pub fn handle_deref(&mut self) -> Result<(), NasmError> {
@@ -12,6 +12,20 @@ impl<'a> Generator<'a> {
         Ok(())
     }
 
+    /// Pushes every slot of the value at the popped address,
+    /// starting with the lowest one.
+    pub fn handle_deref_slots(&mut self, slots: usize) -> Result<(), NasmError> {
+        self.code.add(Row::Pop("rcx".into()));
+
+        for slot in 0..slots {
+            self.code
+                .add(Row::Move("rax".into(), format!("[rcx+{}]", slot * 8)))
+                .add(Row::Push("rax".into()));
+        }
+
+        Ok(())
+    }
+
     /// Pushes the value to the stack.
     /// If `push_address`, use LEA instead of `MOV`.
     pub fn handle_push(
@@ -42,20 +56,42 @@ impl<'a> Generator<'a> {
 
                 self.code.add(Row::Push("rcx".into()))
             }
-            OperandValue::DataLocation(label) => {
+            OperandValue::DataLocation(label, slot) => {
+                let location = match slot {
+                    0 => format!("[{label}]"),
+                    slot => format!("[{label}-{}]", slot * 8),
+                };
+
                 if push_address {
-                    self.code.add(Row::LoadEffectiveAddress(
-                        "rcx".into(),
-                        format!("[{label}]"),
-                    ));
+                    self.code
+                        .add(Row::LoadEffectiveAddress("rcx".into(), location));
                 } else {
-                    self.code.add(Row::Move("rcx".into(), format!("[{label}]")));
+                    self.code.add(Row::Move("rcx".into(), location));
                 }
 
                 self.code.add(Row::Push("rcx".into()))
             }
-            OperandValue::DataPointerLocation(label) => {
-                self.code.add(Row::Push(format!("dword {label}")))
+            // Addresses are relative to the instruction, see `add_header`
+            OperandValue::DataPointerLocation(label) => self
+                .code
+                .add(Row::LoadEffectiveAddress(
+                    "rcx".into(),
+                    format!("[{label}]"),
+                ))
+                .add(Row::Push("rcx".into())),
+            OperandValue::FunctionLocation(function_id) => self
+                .code
+                .add(Row::LoadEffectiveAddress(
+                    "rcx".into(),
+                    format!("[{}]", Self::get_function_name(*function_id)),
+                ))
+                .add(Row::Push("rcx".into())),
+            OperandValue::ArgumentRegister(index) => {
+                if push_address {
+                    todo!("Unsupported");
+                }
+
+                self.code.add(Row::Push(ARG_REG[*index].into()))
             }
         };
 
@@ -40,10 +40,15 @@ impl Display for CodeRow {
 pub enum Row {
     Comment(String),
     Extern(String),
+    Default(String),      // Addressing mode of memory operands without a register
     Move(String, String), // todo: types for numbers, registers, or labels
+    MoveSignExtend(String, String),
+    MoveZeroExtend(String, String),
+    MoveSignExtendDoubleWord(String, String),
     LoadEffectiveAddress(String, String),
     And(String, String),
     Xor(String, String),
+    ShiftRight(String, String),
     Section(String),
     Label(String),
     Global(String),
@@ -58,8 +63,10 @@ pub enum Row {
     JumpIfNotEquals(String),
     JumpIfLessThan(String),
     JumpIfGreaterThan(String),
+    JumpIfBelow(String), // Unsigned less than
     // DeclareByte(String), // todo: allow for all allowed values: https://www.nasm.us/doc/nasmdoc3.html 3.2.1
     DeclareStaticString(String), // Declare byte abstraction, completes it with the string length
+    DeclareQuadWords(Vec<String>),
     ReserveBytes(usize),
     Push(String),
     Pop(String),
@@ -83,10 +90,15 @@ impl Display for Row {
         match self {
             Row::Comment(comment) => w(format_args!("\t; {}", *comment)),
             Row::Extern(ext) => w(format_args!("\textern {ext}")),
+            Row::Default(mode) => w(format_args!("\tdefault {mode}")),
             Row::Move(to, from) => w(format_args!("\tmov {to}, {from}")),
+            Row::MoveSignExtend(to, from) => w(format_args!("\tmovsx {to}, {from}")),
+            Row::MoveZeroExtend(to, from) => w(format_args!("\tmovzx {to}, {from}")),
+            Row::MoveSignExtendDoubleWord(to, from) => w(format_args!("\tmovsxd {to}, {from}")),
             Row::LoadEffectiveAddress(to, from) => w(format_args!("\tlea {to}, {from}")),
             Row::And(to, from) => w(format_args!("\tand {to}, {from}")),
             Row::Xor(to, from) => w(format_args!("\txor {to}, {from}")),
+            Row::ShiftRight(to, by) => w(format_args!("\tshr {to}, {by}")),
             Row::Section(section) => w(format_args!("\n\tsection .{section}")),
             Row::Label(label) => w(format_args!("{label}:")),
             Row::Push(operand) => w(format_args!("\tpush {operand}")),
@@ -98,12 +110,14 @@ impl Display for Row {
             Row::JumpIfNotEquals(label) => w(format_args!("\tjne {label}")),
             Row::JumpIfLessThan(label) => w(format_args!("\tjl {label}")),
             Row::JumpIfGreaterThan(label) => w(format_args!("\tjg {label}")),
+            Row::JumpIfBelow(label) => w(format_args!("\tjb {label}")),
             Row::Subtract(to, from) => w(format_args!("\tsub {to}, {from}")),
             Row::Multiply(to) => w(format_args!("\tmul {to}")),
             Row::Divide(divisor) => w(format_args!("\tidiv {divisor}")),
             Row::Global(global) => w(format_args!("\tglobal {global}")),
             Row::Call(function) => w(format_args!("\tcall {function}")),
             Row::DeclareStaticString(s) => w(format_args!("\tdb {}, 0", get_bytes(s))),
+            Row::DeclareQuadWords(values) => w(format_args!("\tdq {}", values.join(", "))),
             Row::ReserveBytes(i) => w(format_args!("\tresb {i}")),
             Row::Ret => w(format_args!("\tret")),
         }
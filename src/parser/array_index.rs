@@ -1,30 +1,55 @@
 use crate::lexer::Keyword;
 
 use super::{
-    definition::{ArrayIndex, Expression, ExpressionKind},
+    definition::{ArrayIndex, ArraySlice, Expression, ExpressionKind},
     error::{ParserError, ParserErrorKind},
     Parser,
 };
 
 impl<'a> Parser<'a> {
+    /// Parses an optional slice bound, which is omitted if the next token is `terminator`.
+    fn slice_bound(
+        &mut self,
+        terminator: &'static [Keyword],
+    ) -> Result<Option<Box<Expression>>, ParserError> {
+        if self.get_peek(terminator).is_some() {
+            return Ok(None);
+        }
+
+        Ok(Some(Box::new(self.expression()?)))
+    }
+
     pub fn index_from(&mut self, mut expr: Expression) -> Result<Expression, ParserError> {
         while let Some(open) = self.get(&[Keyword::BracketLeft]) {
-            let index = self.expression()?;
-
-            if let Some(close) = self.get(&[Keyword::BracketRight]) {
-                expr = Expression {
-                    pos: expr.pos.start..close.pos.end,
-                    kind: ExpressionKind::ArrayIndex(ArrayIndex {
-                        left: Box::new(expr),
-                        index: Box::new(index),
-                    }),
-                };
+            let start = expr.pos.start;
+            let index = self.slice_bound(&[Keyword::DotDot])?;
+
+            let kind = if self.get(&[Keyword::DotDot]).is_some() {
+                let end = self.slice_bound(&[Keyword::BracketRight])?;
+
+                ExpressionKind::ArraySlice(ArraySlice {
+                    left: Box::new(expr),
+                    start: index,
+                    end,
+                })
             } else {
+                ExpressionKind::ArrayIndex(ArrayIndex {
+                    left: Box::new(expr),
+                    index: index.expect("The index is only omitted before `..`"),
+                })
+            };
+
+            let Some(close) = self.get(&[Keyword::BracketRight]) else {
                 return Err(ParserError::new(
                     open.pos.clone(),
                     ParserErrorKind::UnterminatedPair(Keyword::BracketLeft),
                 ));
-            }
+            };
+
+            expr = Expression {
+                pos: start..close.pos.end,
+                kind,
+            };
         }
 
         Ok(expr)
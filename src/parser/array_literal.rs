@@ -0,0 +1,55 @@
+use crate::lexer::Keyword;
+
+use super::{
+    definition::{ArrayLiteral, Expression, ExpressionKind},
+    error::{ParserError, ParserErrorKind},
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub fn array_literal(&mut self) -> Result<Expression, ParserError> {
+        let Some(open) = self.get(&[Keyword::BracketLeft]) else {
+            return self.parenthesis();
+        };
+
+        let mut elements = Vec::new();
+
+        let close = loop {
+            if self.is_end() {
+                return Err(ParserError::new(
+                    open.pos.clone(),
+                    ParserErrorKind::UnterminatedPair(Keyword::BracketLeft),
+                ));
+            }
+
+            if let Some(close) = self.get(&[Keyword::BracketRight]) {
+                break close;
+            }
+
+            elements.push(self.expression()?);
+
+            // `[value; length]`
+            if elements.len() == 1 && self.get(&[Keyword::Semicolon]).is_some() {
+                let length = self.parse_array_length()?;
+                let close = self.expect(&[Keyword::BracketRight])?;
+
+                return Ok(Expression {
+                    pos: open.pos.start..close.pos.end,
+                    kind: ExpressionKind::ArrayLiteral(ArrayLiteral::Repeat(
+                        Box::new(elements.pop().unwrap()),
+                        length,
+                    )),
+                });
+            }
+
+            if self.get_peek(&[Keyword::BracketRight]).is_none() {
+                self.expect(&[Keyword::Comma])?;
+            }
+        };
+
+        Ok(Expression {
+            pos: open.pos.start..close.pos.end,
+            kind: ExpressionKind::ArrayLiteral(ArrayLiteral::List(elements)),
+        })
+    }
+}
@@ -4,7 +4,9 @@ use crate::{
 };
 
 use super::{
-    definition::{Expression, Statement, StatementKind, VariableDeclaration},
+    definition::{
+        Expression, Statement, StatementKind, TupleBinding, TupleDeclaration, VariableDeclaration,
+    },
     error::{ParserError, ParserErrorKind},
     types::Type,
     util::get_block_identifier,
@@ -20,18 +22,75 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses `(a, mut b) = value`, where `let (` has already been consumed.
+    fn tuple_declaration(&mut self, open: &Block) -> Result<Statement, ParserError> {
+        let mut bindings = Vec::new();
+
+        loop {
+            let mutable = self.get(&[Keyword::Mut]).is_some();
+            let identifier = self.expect(&[Keyword::Identifier])?;
+
+            bindings.push(TupleBinding {
+                identifier: get_block_identifier(identifier).unwrap(),
+                identifier_pos: identifier.pos.clone(),
+                mutable,
+            });
+
+            if self.get(&[Keyword::Comma]).is_none()
+                || self.get_peek(&[Keyword::ParRight]).is_some()
+            {
+                break;
+            }
+        }
+
+        let close = self.expect(&[Keyword::ParRight])?;
+
+        let Some((right, _)) = self.parse_assignment_value()? else {
+            return Err(ParserError::new(
+                self.peek_or_eof()?.pos.clone(),
+                ParserErrorKind::Expected(&[Keyword::Equals]),
+            ));
+        };
+
+        Ok(Statement {
+            pos: open.pos.start..right.pos.end,
+            kind: StatementKind::TupleDeclaration(TupleDeclaration {
+                value: VariableDeclaration {
+                    typ: None,
+                    identifier: ".tuple".to_string(),
+                    mutable: false,
+                    identifier_pos: open.pos.start..close.pos.end,
+                    right: Some(Box::new(right)),
+                },
+                bindings,
+            }),
+        })
+    }
+
     fn new_assignment(&mut self) -> Result<Statement, ParserError> {
+        if let Some(open) = self.get(&[Keyword::ParLeft]) {
+            return self.tuple_declaration(open);
+        }
+
+        let mutable = self.get(&[Keyword::Mut]).is_some();
+
         if let Some(left) = self.get(&[Keyword::Identifier]) {
             let assignment_type: Option<Type> = self
                 .get(&[Keyword::Colon])
                 .map(|_| self.parse_type())
                 .transpose()?;
 
-            let Some((right, _)) = self.parse_assignment_value()? else {
-                return Err(ParserError::new(
-                    self.peek_or_eof()?.pos.clone(),
-                    ParserErrorKind::Expected(&[Keyword::Equals]),
-                ));
+            let right = self.parse_assignment_value()?.map(|(right, _)| right);
+
+            let end = match (&right, &assignment_type) {
+                (Some(right), _) => right.pos.end,
+                (None, Some(typ)) => typ.pos.end,
+                (None, None) => {
+                    return Err(ParserError::new(
+                        self.peek_or_eof()?.pos.clone(),
+                        ParserErrorKind::Expected(&[Keyword::Equals]),
+                    ))
+                }
             };
 
             let Some(identifier) = get_block_identifier(left) else {
@@ -42,13 +101,13 @@ impl<'a> Parser<'a> {
             };
 
             Ok(Statement {
-                pos: left.pos.start..right.pos.end,
+                pos: left.pos.start..end,
                 kind: StatementKind::VariableDeclaration(VariableDeclaration {
                     typ: assignment_type,
                     identifier,
+                    mutable,
                     identifier_pos: left.pos.clone(),
-                    right_pos: right.pos.clone(),
-                    right: Box::new(right),
+                    right: right.map(Box::new),
                 }),
             })
         } else {
@@ -0,0 +1,27 @@
+use crate::lexer::Keyword;
+
+use super::{
+    definition::{Cast, Expression, ExpressionKind},
+    error::ParserError,
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub fn cast(&mut self) -> Result<Expression, ParserError> {
+        let mut expr = self.struct_contruction()?;
+
+        while self.get(&[Keyword::As]).is_some() {
+            let typ = self.parse_type()?;
+
+            expr = Expression {
+                pos: expr.pos.start..typ.pos.end,
+                kind: ExpressionKind::Cast(Cast {
+                    expr: Box::new(expr),
+                    typ,
+                }),
+            }
+        }
+
+        Ok(expr)
+    }
+}
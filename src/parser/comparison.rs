@@ -10,9 +10,12 @@ impl<'a> Parser<'a> {
     pub fn comparison(&mut self) -> Result<Expression, ParserError> {
         let left = self.addition()?;
 
-        if let Some(operator) =
-            self.get(&[Keyword::LessThan, Keyword::GreaterThan, Keyword::Equality])
-        {
+        if let Some(operator) = self.get(&[
+            Keyword::LessThan,
+            Keyword::GreaterThan,
+            Keyword::Equality,
+            Keyword::NotEqual,
+        ]) {
             let right = self.addition()?;
             let pos = left.pos.start..right.pos.end;
 
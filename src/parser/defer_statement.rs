@@ -0,0 +1,24 @@
+use crate::lexer::Keyword;
+
+use super::{
+    definition::{DeferStatement, Statement, StatementKind},
+    error::ParserError,
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub fn defer_statement(&mut self) -> Result<Statement, ParserError> {
+        if let Some(defer) = self.get(&[Keyword::Defer]) {
+            let expr = self.expression()?;
+
+            return Ok(Statement {
+                pos: defer.pos.start..expr.pos.end,
+                kind: StatementKind::DeferStatement(DeferStatement {
+                    value: Box::new(expr),
+                }),
+            });
+        }
+
+        self.assignment()
+    }
+}
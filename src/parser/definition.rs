@@ -1,7 +1,4 @@
-use std::{
-    collections::{BTreeMap, HashMap},
-    ops::Range,
-};
+use std::ops::Range;
 
 use crate::lexer::{Keyword, Literal};
 
@@ -20,34 +17,91 @@ pub enum DeclarationKind {
     Statement(Statement),
     StructDeclaration(StructDeclaration),
     FunctionDeclaration(FunctionDeclaration),
+    // `extern fn puts(s: &char) -> int;`, without content
+    ExternFunction(FunctionDeclaration),
+    TypeAlias(TypeAlias),
+    TraitDeclaration(TraitDeclaration),
+    ImplDeclaration(ImplDeclaration),
 }
 
 #[derive(Debug)]
 pub struct StructField {
     pub typ: Type,
     pub pos: Range<usize>,
+    pub default: Option<Expression>,
 }
 
 #[derive(Debug)]
 pub struct StructDeclaration {
     pub identifier: String,
-    pub fields: BTreeMap<String, StructField>,
+    pub identifier_pos: Range<usize>,
+    // In declaration order, which is also the order of the fields in memory
+    pub fields: Vec<(String, StructField)>,
+}
+
+#[derive(Debug)]
+pub struct TypeAlias {
+    pub identifier: String,
+    pub identifier_pos: Range<usize>,
+    pub typ: Type,
 }
 
 #[derive(Debug, Clone)]
 pub struct FunctionDeclarationParameter {
     pub identifier: String,
+    pub mutable: bool,
     pub typ: Type,
     pub pos: Range<usize>,
 }
 
+/// `T: Show + Debug` in `fn name<T: Show + Debug>()`.
+#[derive(Debug)]
+pub struct GenericParameter {
+    pub identifier: String,
+    pub identifier_pos: Range<usize>,
+    // Traits which the type has to implement
+    pub bounds: Vec<(String, Range<usize>)>,
+}
+
 #[derive(Debug)]
 pub struct FunctionDeclaration {
     pub identifier: String,
     pub identifier_pos: Range<usize>,
+    pub generics: Vec<GenericParameter>,
     pub parameters: Vec<FunctionDeclarationParameter>,
     pub content: Vec<Declaration>,
     pub return_type: Option<Type>,
+
+    // Takes any amount of arguments after the parameters, `...`
+    pub variadic: bool,
+
+    // Declared with `export fn`, callable from outside the program by its identifier
+    pub exported: bool,
+}
+
+/// `fn show(self: &Self);` in a trait declaration.
+#[derive(Debug, Clone)]
+pub struct TraitMethod {
+    pub identifier: String,
+    pub identifier_pos: Range<usize>,
+    pub parameters: Vec<FunctionDeclarationParameter>,
+    pub return_type: Option<Type>,
+}
+
+#[derive(Debug)]
+pub struct TraitDeclaration {
+    pub identifier: String,
+    pub identifier_pos: Range<usize>,
+    pub methods: Vec<TraitMethod>,
+}
+
+/// `impl Trait for Type { ... }`, or `impl Type { ... }` for methods outside of a trait.
+#[derive(Debug)]
+pub struct ImplDeclaration {
+    pub trait_identifier: Option<(String, Range<usize>)>,
+    pub typ: Type,
+    // Function declarations
+    pub methods: Vec<Declaration>,
 }
 
 #[derive(Debug)]
@@ -60,9 +114,12 @@ pub struct Statement {
 pub struct VariableDeclaration {
     pub typ: Option<Type>,
     pub identifier: String,
+    pub mutable: bool,
     pub identifier_pos: Range<usize>,
-    pub right: Box<Expression>,
-    pub right_pos: Range<usize>,
+
+    // None if the variable is declared without a value,
+    // which requires the type to be given.
+    pub right: Option<Box<Expression>>,
 }
 
 #[derive(Debug)]
@@ -78,9 +135,26 @@ pub enum StatementKind {
     Expression(Expression),
     VariableDeclaration(VariableDeclaration),
     VariableAssignment(VariableAssignment),
+    TupleDeclaration(TupleDeclaration),
     IfStatements(Vec<IfStatement>),
     WhileStatement(WhileStatement),
     ReturnStatement(ReturnStatement),
+    DeferStatement(DeferStatement),
+}
+
+/// `let (a, mut b) = value;`
+#[derive(Debug)]
+pub struct TupleDeclaration {
+    // The value is stored in a hidden variable, which the bindings are read from
+    pub value: VariableDeclaration,
+    pub bindings: Vec<TupleBinding>,
+}
+
+#[derive(Debug)]
+pub struct TupleBinding {
+    pub identifier: String,
+    pub identifier_pos: Range<usize>,
+    pub mutable: bool,
 }
 
 #[derive(Debug)]
@@ -97,6 +171,13 @@ pub struct IfStatement {
 
 #[derive(Debug)]
 pub struct ReturnStatement {
+    // None for `return;`
+    pub value: Option<Box<Expression>>,
+}
+
+/// `defer expr;`, evaluated when the enclosing scope is exited.
+#[derive(Debug)]
+pub struct DeferStatement {
     pub value: Box<Expression>,
 }
 
@@ -123,7 +204,12 @@ pub enum ExpressionKind {
     MemberAccess(MemberAccess),
     FunctionCall(FunctionCall),
     ArrayIndex(ArrayIndex),
+    ArraySlice(ArraySlice),
     StructConstruction(StructConstruction),
+    Cast(Cast),
+    TypeInfo(TypeInfo),
+    ArrayLiteral(ArrayLiteral),
+    Tuple(Vec<Expression>),
 }
 
 #[derive(Debug)]
@@ -138,6 +224,14 @@ pub struct ArrayIndex {
     pub index: Box<Expression>,
 }
 
+/// `left[start..end]`, where both bounds are optional.
+#[derive(Debug)]
+pub struct ArraySlice {
+    pub left: Box<Expression>,
+    pub start: Option<Box<Expression>>,
+    pub end: Option<Box<Expression>>,
+}
+
 #[derive(Debug)]
 pub struct FunctionCall {
     pub left: Box<Expression>,
@@ -155,7 +249,41 @@ pub struct StructConstructionField {
 pub struct StructConstruction {
     pub identifier: String,
     pub identifier_pos: Range<usize>,
-    pub fields: HashMap<String, StructConstructionField>,
+    pub fields: Vec<(String, StructConstructionField)>,
+    // Omitted fields are taken from this value, `..base`
+    pub base: Option<Box<Expression>>,
+}
+
+#[derive(Debug)]
+pub struct Cast {
+    pub expr: Box<Expression>,
+    pub typ: Type,
+}
+
+#[derive(Debug)]
+pub enum ArrayLiteral {
+    // `[a, b, c]`
+    List(Vec<Expression>),
+
+    // `[value; length]`
+    Repeat(Box<Expression>, usize),
+}
+
+#[derive(Debug)]
+pub enum TypeInfoArgument {
+    Type(Type),
+    Expression(Box<Expression>),
+}
+
+#[derive(Debug)]
+pub enum TypeInfo {
+    Size(TypeInfoArgument),
+    Align(TypeInfoArgument),
+    Offset {
+        typ: Type,
+        field: String,
+        field_pos: Range<usize>,
+    },
 }
 
 #[derive(Debug)]
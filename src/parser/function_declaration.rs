@@ -1,90 +1,198 @@
 use crate::lexer::{Keyword, Token};
 
 use super::{
-    definition::{Declaration, DeclarationKind, FunctionDeclaration, FunctionDeclarationParameter},
+    definition::{
+        Declaration, DeclarationKind, FunctionDeclaration, FunctionDeclarationParameter,
+        GenericParameter,
+    },
     error::{ParserError, ParserErrorKind},
     util::get_block_identifier,
     Parser,
 };
 
 impl<'a> Parser<'a> {
-    pub fn function_declaration(&mut self) -> Result<Declaration, ParserError> {
-        if self.get(&[Keyword::Fn]).is_some() {
-            let fn_identifier = self.expect(&[Keyword::Identifier])?;
-            let identifier = match get_block_identifier(fn_identifier) {
-                Some(identifier) => identifier,
-                _ => {
-                    return Err(ParserError::new(
-                        fn_identifier.pos.clone(),
-                        ParserErrorKind::Expected(&[Keyword::Identifier]),
-                    ))
+    /// Parses `<T: Show + Debug, U>`, where `<` has already been consumed.
+    fn generic_parameters(&mut self) -> Result<Vec<GenericParameter>, ParserError> {
+        let mut generics = Vec::new();
+
+        loop {
+            let block = self.expect(&[Keyword::Identifier])?;
+            let identifier = get_block_identifier(block).unwrap();
+            let mut bounds = Vec::new();
+
+            if self.get(&[Keyword::Colon]).is_some() {
+                loop {
+                    let bound = self.expect(&[Keyword::Identifier])?;
+                    bounds.push((get_block_identifier(bound).unwrap(), bound.pos.clone()));
+
+                    if self.get(&[Keyword::Plus]).is_none() {
+                        break;
+                    }
                 }
-            };
+            }
+
+            generics.push(GenericParameter {
+                identifier,
+                identifier_pos: block.pos.clone(),
+                bounds,
+            });
+
+            if self.get(&[Keyword::Comma]).is_none() {
+                break;
+            }
+        }
 
-            let par_open = self.expect(&[Keyword::ParLeft])?;
-            let mut parameters = Vec::new();
+        self.expect(&[Keyword::GreaterThan])?;
+
+        Ok(generics)
+    }
+
+    /// Parses everything of a function declaration up until its body.
+    /// Returns the declaration without content, and the end of the signature.
+    /// Only external functions may be `variadic`.
+    pub fn function_signature(
+        &mut self,
+        variadic: bool,
+    ) -> Result<(FunctionDeclaration, usize), ParserError> {
+        let fn_identifier = self.expect(&[Keyword::Identifier])?;
+        let identifier = match get_block_identifier(fn_identifier) {
+            Some(identifier) => identifier,
+            _ => {
+                return Err(ParserError::new(
+                    fn_identifier.pos.clone(),
+                    ParserErrorKind::Expected(&[Keyword::Identifier]),
+                ))
+            }
+        };
+
+        let generics = match self.get(&[Keyword::LessThan]) {
+            Some(_) => self.generic_parameters()?,
+            None => Vec::new(),
+        };
+
+        let par_open = self.expect(&[Keyword::ParLeft])?;
+        let mut parameters = Vec::new();
+        let mut is_variadic = false;
+
+        loop {
+            if self.is_end() {
+                return Err(ParserError::new(
+                    par_open.pos.clone(),
+                    ParserErrorKind::UnterminatedPair(Keyword::ParLeft),
+                ));
+            }
+
+            if self.get(&[Keyword::Comma]).is_none() {
+                if let Some(close) = self.get(&[Keyword::ParRight]) {
+                    let return_type = self
+                        .get(&[Keyword::Arrow])
+                        .map(|_| self.parse_type())
+                        .transpose()?;
+
+                    return Ok((
+                        FunctionDeclaration {
+                            identifier,
+                            identifier_pos: fn_identifier.pos.clone(),
+                            generics,
+                            parameters,
+                            content: Vec::new(),
+                            return_type,
+                            variadic: is_variadic,
+                            exported: false,
+                        },
+                        close.pos.end,
+                    ));
+                }
+            }
 
-            loop {
-                if self.is_end() {
+            // `...` has to be the last parameter
+            if let Some(ellipsis) = self.get(&[Keyword::Ellipsis]) {
+                if !variadic {
                     return Err(ParserError::new(
-                        par_open.pos.clone(),
-                        ParserErrorKind::UnterminatedPair(Keyword::ParLeft),
+                        ellipsis.pos.clone(),
+                        ParserErrorKind::UnexpectedToken(ellipsis.token.clone()),
                     ));
                 }
 
-                if self.get(&[Keyword::Comma]).is_none() {
-                    if let Some(close) = self.get(&[Keyword::ParRight]) {
-                        let return_type = self
-                            .get(&[Keyword::Arrow])
-                            .map(|_| self.parse_type())
-                            .transpose()?;
-
-                        self.expect(&[Keyword::BraceLeft])?;
-                        let mut content: Vec<Declaration> = Vec::new();
-
-                        while self.get(&[Keyword::BraceRight]).is_none() {
-                            content.push(self.declaration()?);
-                        }
-
-                        return Ok(Declaration {
-                            pos: fn_identifier.pos.start..close.pos.end,
-                            kind: DeclarationKind::FunctionDeclaration(FunctionDeclaration {
-                                identifier,
-                                identifier_pos: fn_identifier.pos.clone(),
-                                parameters,
-                                content,
-                                return_type,
-                            }),
-                        });
-                    }
+                if self.get_peek(&[Keyword::ParRight]).is_none() {
+                    return Err(ParserError::new(
+                        ellipsis.pos.clone(),
+                        ParserErrorKind::Expected(&[Keyword::ParRight]),
+                    ));
                 }
 
-                let (par_identifier, par_identifier_pos) =
-                    if let Some(identifier) = self.get(&[Keyword::Identifier]) {
-                        if let Token::Identifier(ref s) = identifier.token {
-                            (s, &identifier.pos)
-                        } else {
-                            return Err(ParserError::new(
-                                fn_identifier.pos.clone(),
-                                ParserErrorKind::Expected(&[Keyword::Identifier]),
-                            ));
-                        }
+                is_variadic = true;
+                continue;
+            }
+
+            let mutable = self.get(&[Keyword::Mut]).is_some();
+
+            let (par_identifier, par_identifier_pos) =
+                if let Some(identifier) = self.get(&[Keyword::Identifier]) {
+                    if let Token::Identifier(ref s) = identifier.token {
+                        (s, &identifier.pos)
                     } else {
                         return Err(ParserError::new(
                             fn_identifier.pos.clone(),
                             ParserErrorKind::Expected(&[Keyword::Identifier]),
                         ));
-                    };
+                    }
+                } else {
+                    return Err(ParserError::new(
+                        fn_identifier.pos.clone(),
+                        ParserErrorKind::Expected(&[Keyword::Identifier]),
+                    ));
+                };
 
-                self.expect(&[Keyword::Colon])?;
-                let par_type = self.parse_type()?;
+            self.expect(&[Keyword::Colon])?;
+            let par_type = self.parse_type()?;
 
-                parameters.push(FunctionDeclarationParameter {
-                    identifier: par_identifier.clone(),
-                    typ: par_type,
-                    pos: par_identifier_pos.clone(),
-                });
+            parameters.push(FunctionDeclarationParameter {
+                identifier: par_identifier.clone(),
+                mutable,
+                typ: par_type,
+                pos: par_identifier_pos.clone(),
+            });
+        }
+    }
+
+    pub fn function_declaration(&mut self) -> Result<Declaration, ParserError> {
+        if let Some(extern_block) = self.get(&[Keyword::Extern]) {
+            self.expect(&[Keyword::Fn])?;
+            let (declaration, end) = self.function_signature(true)?;
+
+            if self.get(&[Keyword::Semicolon]).is_none() {
+                return Err(ParserError::new(
+                    extern_block.pos.start..end,
+                    ParserErrorKind::ExpectedSemicolon,
+                ));
+            }
+
+            return Ok(Declaration {
+                pos: extern_block.pos.start..end,
+                kind: DeclarationKind::ExternFunction(declaration),
+            });
+        }
+
+        let exported = self.get(&[Keyword::Export]).is_some();
+        if exported {
+            self.expect(&[Keyword::Fn])?;
+        }
+
+        if exported || self.get(&[Keyword::Fn]).is_some() {
+            let (mut declaration, end) = self.function_signature(false)?;
+            declaration.exported = exported;
+
+            self.expect(&[Keyword::BraceLeft])?;
+
+            while self.get(&[Keyword::BraceRight]).is_none() {
+                declaration.content.push(self.declaration()?);
             }
+
+            return Ok(Declaration {
+                pos: declaration.identifier_pos.start..end,
+                kind: DeclarationKind::FunctionDeclaration(declaration),
+            });
         }
 
         let statement = self.statement()?;
@@ -0,0 +1,70 @@
+use crate::lexer::Keyword;
+
+use super::{
+    definition::{Declaration, DeclarationKind, ImplDeclaration},
+    error::{ParserError, ParserErrorKind},
+    types::{Type, TypeIdentifier},
+    util::get_block_identifier,
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub fn impl_declaration(&mut self) -> Result<Declaration, ParserError> {
+        if let Some(impl_block) = self.get(&[Keyword::Impl]) {
+            let first = self.expect(&[Keyword::Identifier])?;
+            let first_identifier = get_block_identifier(first).unwrap();
+
+            // `impl Trait for Type`, otherwise the identifier is the type itself
+            let (trait_identifier, typ) = if self.get(&[Keyword::For]).is_some() {
+                (
+                    Some((first_identifier, first.pos.clone())),
+                    self.parse_type()?,
+                )
+            } else {
+                let typ = Type {
+                    identifier: TypeIdentifier::Struct(first_identifier),
+                    pos: first.pos.clone(),
+                    children: None,
+                };
+
+                (None, typ)
+            };
+
+            let open = self.expect(&[Keyword::BraceLeft])?;
+            let mut methods = Vec::new();
+
+            let close = loop {
+                if self.is_end() {
+                    return Err(ParserError::new(
+                        open.pos.clone(),
+                        ParserErrorKind::UnterminatedPair(Keyword::BraceLeft),
+                    ));
+                }
+
+                if let Some(close) = self.get(&[Keyword::BraceRight]) {
+                    break close;
+                }
+
+                if self.get_peek(&[Keyword::Fn]).is_none() {
+                    return Err(ParserError::new(
+                        self.peek_or_eof()?.pos.clone(),
+                        ParserErrorKind::Expected(&[Keyword::Fn]),
+                    ));
+                }
+
+                methods.push(self.function_declaration()?);
+            };
+
+            return Ok(Declaration {
+                pos: impl_block.pos.start..close.pos.end,
+                kind: DeclarationKind::ImplDeclaration(ImplDeclaration {
+                    trait_identifier,
+                    typ,
+                    methods,
+                }),
+            });
+        }
+
+        self.type_alias()
+    }
+}
@@ -1,7 +1,7 @@
-use crate::lexer::Keyword;
+use crate::lexer::{Keyword, Literal};
 
 use super::{
-    definition::{Expression, ExpressionKind, MemberAccess},
+    definition::{Expression, ExpressionKind, MemberAccess, Primary},
     error::ParserError,
     util::get_expr_identifier,
     Parser,
@@ -14,7 +14,15 @@ impl<'a> Parser<'a> {
         while self.get(&[Keyword::Dot]).is_some() {
             let next = self.primary()?;
 
-            if let Some(identifier) = get_expr_identifier(&next) {
+            // Tuple elements are accessed by index, `.0`
+            let identifier = match &next.kind {
+                ExpressionKind::Primary(Primary::Literal(Literal::Int(index))) => {
+                    Some(index.to_string())
+                }
+                _ => get_expr_identifier(&next),
+            };
+
+            if let Some(identifier) = identifier {
                 expr = Expression {
                     pos: expr.pos.start..next.pos.end,
                     kind: ExpressionKind::MemberAccess(MemberAccess {
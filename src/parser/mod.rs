@@ -11,12 +11,16 @@ pub mod util;
 
 mod addition;
 mod assignment;
+mod cast;
 mod comparison;
+mod defer_statement;
 mod error;
 mod function_call;
 mod function_declaration;
 mod if_statement;
+mod impl_declaration;
 mod array_index;
+mod array_literal;
 mod member;
 mod multiplication;
 mod parenthesis;
@@ -25,6 +29,9 @@ mod reference;
 mod return_statement;
 mod struct_construction;
 mod struct_declaration;
+mod trait_declaration;
+mod type_alias;
+mod type_info;
 mod unexpected;
 mod while_statement;
 
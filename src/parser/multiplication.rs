@@ -8,10 +8,10 @@ use super::{
 
 impl<'a> Parser<'a> {
     pub fn multiplication(&mut self) -> Result<Expression, ParserError> {
-        let mut expr = self.struct_contruction()?;
+        let mut expr = self.cast()?;
 
         while let Some(block) = self.get(&[Keyword::Asterix, Keyword::Slash]) {
-            let right = self.struct_contruction()?;
+            let right = self.cast()?;
             let pos = expr.pos.start..right.pos.end;
 
             expr = Expression {
@@ -1,7 +1,7 @@
-use crate::lexer::Keyword;
+use crate::lexer::{Block, Keyword};
 
 use super::{
-    definition::Expression,
+    definition::{Expression, ExpressionKind},
     error::{ParserError, ParserErrorKind},
     Parser,
 };
@@ -10,6 +10,11 @@ impl<'a> Parser<'a> {
     pub fn parenthesis(&mut self) -> Result<Expression, ParserError> {
         if let Some(parenthesis) = self.get(&[Keyword::ParLeft]) {
             let expr = self.expression()?;
+
+            if self.get_peek(&[Keyword::Comma]).is_some() {
+                return self.tuple(parenthesis, expr);
+            }
+
             if self.get(&[Keyword::ParRight]).is_none() && self.is_end() {
                 return Err(ParserError::new(
                     parenthesis.pos.clone(),
@@ -22,4 +27,35 @@ impl<'a> Parser<'a> {
 
         self.unexpected()
     }
+
+    /// Parses the rest of `(a, b)` after the first element, `(a,)` is a tuple as well.
+    fn tuple(&mut self, open: &Block, first: Expression) -> Result<Expression, ParserError> {
+        let mut elements = vec![first];
+
+        let close = loop {
+            if self.is_end() {
+                return Err(ParserError::new(
+                    open.pos.clone(),
+                    ParserErrorKind::UnterminatedPair(Keyword::ParLeft),
+                ));
+            }
+
+            if let Some(close) = self.get(&[Keyword::ParRight]) {
+                break close;
+            }
+
+            self.expect(&[Keyword::Comma])?;
+
+            if let Some(close) = self.get(&[Keyword::ParRight]) {
+                break close;
+            }
+
+            elements.push(self.expression()?);
+        };
+
+        Ok(Expression {
+            pos: open.pos.start..close.pos.end,
+            kind: ExpressionKind::Tuple(elements),
+        })
+    }
 }
@@ -18,6 +18,7 @@ impl<'a> Parser<'a> {
                         Literal::String(_) => Keyword::String,
                         Literal::Int(_) => Keyword::Int,
                         Literal::Bool(_) => Keyword::Bool,
+                        Literal::Null => Keyword::Null,
                     };
 
                     (typ, Primary::Literal(literal.clone()))
@@ -36,6 +37,13 @@ impl<'a> Parser<'a> {
             });
         }
 
-        self.parenthesis()
+        if let Some(null) = self.get(&[Keyword::Null]) {
+            return Ok(Expression {
+                pos: null.pos.clone(),
+                kind: ExpressionKind::Primary(Primary::Literal(Literal::Null)),
+            });
+        }
+
+        self.type_info()
     }
 }
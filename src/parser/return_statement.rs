@@ -9,16 +9,24 @@ use super::{
 impl<'a> Parser<'a> {
     pub fn return_statement(&mut self) -> Result<Statement, ParserError> {
         if let Some(ret) = self.get(&[Keyword::Return]) {
+            // `return;` in functions without a return type
+            if self.get_peek(&[Keyword::Semicolon]).is_some() {
+                return Ok(Statement {
+                    pos: ret.pos.clone(),
+                    kind: StatementKind::ReturnStatement(ReturnStatement { value: None }),
+                });
+            }
+
             let expr = self.expression()?;
 
             return Ok(Statement {
                 pos: ret.pos.start..expr.pos.end,
                 kind: StatementKind::ReturnStatement(ReturnStatement {
-                    value: Box::new(expr),
+                    value: Some(Box::new(expr)),
                 }),
             });
         }
 
-        self.assignment()
+        self.defer_statement()
     }
 }
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use crate::lexer::{Keyword, Token};
 
 use super::{
@@ -22,7 +20,8 @@ impl<'a> Parser<'a> {
 
         self.advance_n(2);
 
-        let mut fields = HashMap::new();
+        let mut fields = Vec::new();
+        let mut base = None;
 
         loop {
             if self.is_end() {
@@ -32,6 +31,17 @@ impl<'a> Parser<'a> {
                 ));
             }
 
+            // Every field may have a default value
+            if self.get_peek(&[Keyword::BraceRight]).is_some() {
+                break;
+            }
+
+            // The base has to come last
+            if self.get(&[Keyword::DotDot]).is_some() {
+                base = Some(Box::new(self.expression()?));
+                break;
+            }
+
             let (field_identifier, field_identifier_pos) =
                 if let Some(identifier) = self.get(&[Keyword::Identifier]) {
                     if let Token::Identifier(ref s) = identifier.token {
@@ -52,21 +62,14 @@ impl<'a> Parser<'a> {
 
             self.expect(&[Keyword::Colon])?;
 
-            if let Some(existing_field) = fields.insert(
+            // Duplicates are reported by the compiler
+            fields.push((
                 field_identifier.clone(),
                 StructConstructionField {
                     expr: self.expression()?,
                     pos: field_identifier_pos.clone(),
                 },
-            ) {
-                return Err(ParserError::new(
-                    field_identifier_pos.clone(),
-                    ParserErrorKind::FieldAlreadyDefined {
-                        identifier: field_identifier.clone(),
-                        identifier_pos: existing_field.pos,
-                    },
-                ));
-            }
+            ));
 
             if self.get(&[Keyword::Comma]).is_none()
                 || self.get_peek(&[Keyword::BraceRight]).is_some()
@@ -83,6 +86,7 @@ impl<'a> Parser<'a> {
                 identifier: identifier.clone(),
                 identifier_pos: struct_identifier.pos.clone(),
                 fields,
+                base,
             }),
         })
     }
@@ -1,5 +1,3 @@
-use std::collections::BTreeMap;
-
 use crate::lexer::{Keyword, Token};
 
 use super::{
@@ -24,7 +22,7 @@ impl<'a> Parser<'a> {
             };
 
             let open = self.expect(&[Keyword::BraceLeft])?;
-            let mut fields = BTreeMap::new();
+            let mut fields: Vec<(String, StructField)> = Vec::new();
 
             loop {
                 if self.is_end() {
@@ -54,13 +52,32 @@ impl<'a> Parser<'a> {
                 self.expect(&[Keyword::Colon])?;
                 let typ = self.parse_type()?;
 
-                fields.insert(
+                let default = match self.get(&[Keyword::Equals]) {
+                    Some(_) => Some(self.expression()?),
+                    None => None,
+                };
+
+                if let Some((_, existing_field)) = fields
+                    .iter()
+                    .find(|(identifier, _)| identifier == field_identifier)
+                {
+                    return Err(ParserError::new(
+                        field_identifier_pos.clone(),
+                        ParserErrorKind::FieldAlreadyDefined {
+                            identifier: field_identifier.clone(),
+                            identifier_pos: existing_field.pos.clone(),
+                        },
+                    ));
+                }
+
+                fields.push((
                     field_identifier.clone(),
                     StructField {
                         typ,
                         pos: field_identifier_pos.clone(),
+                        default,
                     },
-                );
+                ));
 
                 if self.get(&[Keyword::Comma]).is_none() {
                     break;
@@ -76,10 +93,14 @@ impl<'a> Parser<'a> {
 
             return Ok(Declaration {
                 pos: struct_identifier.pos.start..close.pos.end,
-                kind: DeclarationKind::StructDeclaration(StructDeclaration { identifier, fields }),
+                kind: DeclarationKind::StructDeclaration(StructDeclaration {
+                    identifier,
+                    identifier_pos: struct_identifier.pos.clone(),
+                    fields,
+                }),
             });
         }
 
-        self.function_declaration()
+        self.trait_declaration()
     }
 }
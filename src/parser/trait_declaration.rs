@@ -0,0 +1,71 @@
+use crate::{lexer::Keyword, parser_todo};
+
+use super::{
+    definition::{Declaration, DeclarationKind, TraitDeclaration, TraitMethod},
+    error::{ParserError, ParserErrorKind},
+    util::get_block_identifier,
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub fn trait_declaration(&mut self) -> Result<Declaration, ParserError> {
+        if let Some(trait_block) = self.get(&[Keyword::Trait]) {
+            let trait_identifier = self.expect(&[Keyword::Identifier])?;
+            let Some(identifier) = get_block_identifier(trait_identifier) else {
+                return Err(ParserError::new(
+                    trait_identifier.pos.clone(),
+                    ParserErrorKind::Expected(&[Keyword::Identifier]),
+                ));
+            };
+
+            let open = self.expect(&[Keyword::BraceLeft])?;
+            let mut methods = Vec::new();
+
+            let close = loop {
+                if self.is_end() {
+                    return Err(ParserError::new(
+                        open.pos.clone(),
+                        ParserErrorKind::UnterminatedPair(Keyword::BraceLeft),
+                    ));
+                }
+
+                if let Some(close) = self.get(&[Keyword::BraceRight]) {
+                    break close;
+                }
+
+                // Methods are only declared, their bodies are given by each implementation
+                self.expect(&[Keyword::Fn])?;
+                let (method, end) = self.function_signature(false)?;
+
+                if !method.generics.is_empty() {
+                    return parser_todo!(method.identifier_pos, "Generic trait methods");
+                }
+
+                if self.get(&[Keyword::Semicolon]).is_none() {
+                    return Err(ParserError::new(
+                        method.identifier_pos.start..end,
+                        ParserErrorKind::ExpectedSemicolon,
+                    ));
+                }
+
+                methods.push(TraitMethod {
+                    identifier: method.identifier,
+                    identifier_pos: method.identifier_pos,
+                    parameters: method.parameters,
+                    return_type: method.return_type,
+                });
+            };
+
+            return Ok(Declaration {
+                pos: trait_block.pos.start..close.pos.end,
+                kind: DeclarationKind::TraitDeclaration(TraitDeclaration {
+                    identifier,
+                    identifier_pos: trait_identifier.pos.clone(),
+                    methods,
+                }),
+            });
+        }
+
+        self.impl_declaration()
+    }
+}
@@ -0,0 +1,43 @@
+use crate::lexer::Keyword;
+
+use super::{
+    definition::{Declaration, DeclarationKind, TypeAlias},
+    error::{ParserError, ParserErrorKind},
+    util::get_block_identifier,
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    pub fn type_alias(&mut self) -> Result<Declaration, ParserError> {
+        if let Some(type_block) = self.get(&[Keyword::Type]) {
+            let alias_identifier = self.expect(&[Keyword::Identifier])?;
+            let Some(identifier) = get_block_identifier(alias_identifier) else {
+                return Err(ParserError::new(
+                    alias_identifier.pos.clone(),
+                    ParserErrorKind::Expected(&[Keyword::Identifier]),
+                ));
+            };
+
+            self.expect(&[Keyword::Equals])?;
+            let typ = self.parse_type()?;
+
+            if self.get(&[Keyword::Semicolon]).is_none() {
+                return Err(ParserError::new(
+                    type_block.pos.start..typ.pos.end,
+                    ParserErrorKind::ExpectedSemicolon,
+                ));
+            }
+
+            return Ok(Declaration {
+                pos: type_block.pos.start..typ.pos.end,
+                kind: DeclarationKind::TypeAlias(TypeAlias {
+                    identifier,
+                    identifier_pos: alias_identifier.pos.clone(),
+                    typ,
+                }),
+            });
+        }
+
+        self.function_declaration()
+    }
+}
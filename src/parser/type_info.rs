@@ -0,0 +1,70 @@
+use crate::lexer::{Keyword, Token};
+
+use super::{
+    definition::{Expression, ExpressionKind, TypeInfo, TypeInfoArgument},
+    error::{ParserError, ParserErrorKind},
+    Parser,
+};
+
+impl<'a> Parser<'a> {
+    /// Anything that is not obviously a type is parsed as an expression,
+    /// identifiers are resolved to a type or a variable by the compiler.
+    fn type_info_argument(&mut self) -> Result<TypeInfoArgument, ParserError> {
+        if self
+            .get_peek(&[
+                Keyword::Int,
+                Keyword::Bool,
+                Keyword::Char,
+                Keyword::Pointer,
+                Keyword::Fn,
+            ])
+            .is_some()
+        {
+            return Ok(TypeInfoArgument::Type(self.parse_type()?));
+        }
+
+        Ok(TypeInfoArgument::Expression(Box::new(self.expression()?)))
+    }
+
+    pub fn type_info(&mut self) -> Result<Expression, ParserError> {
+        let Some(keyword) = self.get(&[Keyword::SizeOf, Keyword::AlignOf, Keyword::OffsetOf])
+        else {
+            return self.array_literal();
+        };
+
+        let open = self.expect(&[Keyword::ParLeft])?;
+
+        let info = match keyword.kind {
+            Keyword::SizeOf => TypeInfo::Size(self.type_info_argument()?),
+            Keyword::AlignOf => TypeInfo::Align(self.type_info_argument()?),
+            Keyword::OffsetOf => {
+                let typ = self.parse_type()?;
+                self.expect(&[Keyword::Comma])?;
+
+                let field = self.expect(&[Keyword::Identifier])?;
+                let Token::Identifier(ref identifier) = field.token else {
+                    unreachable!();
+                };
+
+                TypeInfo::Offset {
+                    typ,
+                    field: identifier.clone(),
+                    field_pos: field.pos.clone(),
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        let Some(close) = self.get(&[Keyword::ParRight]) else {
+            return Err(ParserError::new(
+                open.pos.clone(),
+                ParserErrorKind::UnterminatedPair(Keyword::ParLeft),
+            ));
+        };
+
+        Ok(Expression {
+            pos: keyword.pos.start..close.pos.end,
+            kind: ExpressionKind::TypeInfo(info),
+        })
+    }
+}
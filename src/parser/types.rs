@@ -1,16 +1,38 @@
 use std::{fmt::Debug, ops::Range};
 
 use crate::{
-    lexer::{Keyword, Token},
+    lexer::{Block, Keyword, Token},
     parser_todo,
 };
 
-use super::{error::ParserError, Parser};
+use super::{
+    error::{ParserError, ParserErrorKind},
+    util::get_block_identifier,
+    Parser,
+};
 
 #[derive(Debug, Clone)]
 pub enum TypeIdentifier {
     Primitive(Keyword),
     Struct(String),
+
+    // Array of the given length, the element type is the child of the type
+    Array(usize),
+
+    // Slice `&[T]`, the element type is the child of the type
+    Slice,
+
+    // Function pointer, the parameters are the children of the type
+    Function(Option<Box<Type>>),
+
+    // Tuple `(T, U)`, the element types are the children of the type
+    Tuple,
+
+    // Pointer to any type implementing the trait, `&dyn Trait`
+    Dyn(String),
+
+    // Pointer which may be null, `pointer?<T>`, the pointee is the child of the type
+    Nullable,
 }
 
 #[derive(Debug, Clone)]
@@ -21,16 +43,168 @@ pub struct Type {
 }
 
 impl<'a> Parser<'a> {
+    /// Parses `fn(int, bool) -> int`, where `fn` has already been consumed.
+    fn parse_function_type(&mut self, fn_block: &Block) -> Result<Type, ParserError> {
+        let open = self.expect(&[Keyword::ParLeft])?;
+        let mut parameters = Vec::new();
+
+        let close = loop {
+            if self.is_end() {
+                return Err(ParserError::new(
+                    open.pos.clone(),
+                    ParserErrorKind::UnterminatedPair(Keyword::ParLeft),
+                ));
+            }
+
+            if let Some(close) = self.get(&[Keyword::ParRight]) {
+                break close;
+            }
+
+            parameters.push(self.parse_type()?);
+
+            if self.get_peek(&[Keyword::ParRight]).is_none() {
+                self.expect(&[Keyword::Comma])?;
+            }
+        };
+
+        let return_type = self
+            .get(&[Keyword::Arrow])
+            .map(|_| self.parse_type())
+            .transpose()?;
+
+        Ok(Type {
+            pos: fn_block.pos.start
+                ..return_type
+                    .as_ref()
+                    .map(|typ| typ.pos.end)
+                    .unwrap_or(close.pos.end),
+            identifier: TypeIdentifier::Function(return_type.map(Box::new)),
+            children: Some(parameters),
+        })
+    }
+
+    /// Parses a type argument, such as `T` in `pointer<T>`.
+    /// Pointers may point to `const T`, which is represented as
+    /// a `const` type with `T` as its only child.
+    fn parse_type_argument(&mut self, parent: &TypeIdentifier) -> Result<Type, ParserError> {
+        if let TypeIdentifier::Primitive(Keyword::Pointer) | TypeIdentifier::Nullable = parent {
+            if let Some(constant) = self.get(&[Keyword::Const]) {
+                let child = self.parse_type()?;
+
+                return Ok(Type {
+                    identifier: TypeIdentifier::Primitive(Keyword::Const),
+                    pos: constant.pos.start..child.pos.end,
+                    children: Some(vec![child]),
+                });
+            }
+        }
+
+        self.parse_type()
+    }
+
+    /// Parses `(T, U)`, where `(` has already been consumed.
+    /// A type in parenthesis without a comma is not a tuple, `(T,)` is.
+    fn parse_tuple_type(&mut self, open: &Block) -> Result<Type, ParserError> {
+        let mut children = vec![self.parse_type()?];
+        let mut tuple = false;
+
+        while self.get(&[Keyword::Comma]).is_some() {
+            tuple = true;
+
+            if self.get_peek(&[Keyword::ParRight]).is_some() {
+                break;
+            }
+
+            children.push(self.parse_type()?);
+        }
+
+        let close = self.expect(&[Keyword::ParRight])?;
+
+        if !tuple {
+            return Ok(children.pop().unwrap());
+        }
+
+        Ok(Type {
+            identifier: TypeIdentifier::Tuple,
+            pos: open.pos.start..close.pos.end,
+            children: Some(children),
+        })
+    }
+
+    /// Parses `&[T]`, where `&[` has already been consumed.
+    /// `&[T; N]` is still a pointer to an array.
+    fn parse_slice_type(&mut self, ampersand: &Block, open: &Block) -> Result<Type, ParserError> {
+        let child = self.parse_type()?;
+
+        if self.get(&[Keyword::Semicolon]).is_some() {
+            let length = self.parse_array_length()?;
+            let close = self.expect(&[Keyword::BracketRight])?;
+
+            let array = Type {
+                identifier: TypeIdentifier::Array(length),
+                pos: open.pos.start..close.pos.end,
+                children: Some(vec![child]),
+            };
+
+            return Ok(Type {
+                identifier: TypeIdentifier::Primitive(Keyword::Pointer),
+                pos: ampersand.pos.start..close.pos.end,
+                children: Some(vec![array]),
+            });
+        }
+
+        let close = self.expect(&[Keyword::BracketRight])?;
+
+        Ok(Type {
+            identifier: TypeIdentifier::Slice,
+            pos: ampersand.pos.start..close.pos.end,
+            children: Some(vec![child]),
+        })
+    }
+
     pub fn parse_type(&mut self) -> Result<Type, ParserError> {
         let next = self.peek_or_eof()?;
         self.advance();
 
         let identifier = match &next.token {
             Token::Keyword(keyword) => match keyword {
+                Keyword::Pointer if self.get(&[Keyword::QuestionMark]).is_some() => {
+                    TypeIdentifier::Nullable
+                }
                 Keyword::Int | Keyword::Bool | Keyword::Char | Keyword::Pointer => {
                     TypeIdentifier::Primitive(*keyword)
                 }
+                Keyword::Fn => return self.parse_function_type(next),
+                Keyword::ParLeft => return self.parse_tuple_type(next),
+                Keyword::BracketLeft => {
+                    let child = self.parse_type()?;
+                    self.expect(&[Keyword::Semicolon])?;
+                    let length = self.parse_array_length()?;
+                    let close = self.expect(&[Keyword::BracketRight])?;
+
+                    return Ok(Type {
+                        identifier: TypeIdentifier::Array(length),
+                        pos: next.pos.start..close.pos.end,
+                        children: Some(vec![child]),
+                    });
+                }
                 Keyword::Ampersand => {
+                    if let Some(open) = self.get(&[Keyword::BracketLeft]) {
+                        return self.parse_slice_type(next, open);
+                    }
+
+                    if self.get(&[Keyword::Dyn]).is_some() {
+                        let trait_block = self.expect(&[Keyword::Identifier])?;
+
+                        return Ok(Type {
+                            identifier: TypeIdentifier::Dyn(
+                                get_block_identifier(trait_block).unwrap(),
+                            ),
+                            pos: next.pos.start..trait_block.pos.end,
+                            children: None,
+                        });
+                    }
+
                     let child = self.parse_type()?;
 
                     return Ok(Type {
@@ -46,9 +220,9 @@ impl<'a> Parser<'a> {
         };
 
         if let Some(lt) = self.get(&[Keyword::LessThan]) {
-            let mut children = vec![self.parse_type()?];
+            let mut children = vec![self.parse_type_argument(&identifier)?];
             while self.get(&[Keyword::Comma]).is_some() {
-                children.push(self.parse_type()?);
+                children.push(self.parse_type_argument(&identifier)?);
             }
 
             if self.get(&[Keyword::GreaterThan]).is_none() {
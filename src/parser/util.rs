@@ -1,13 +1,13 @@
 use std::ops::Range;
 
 use crate::{
-    lexer::{Block, Keyword, Token},
+    lexer::{Block, Keyword, Literal, Token},
     parser_todo,
 };
 
 use super::{
     definition::{Declaration, Expression, ExpressionKind, Primary},
-    error::ParserError,
+    error::{ParserError, ParserErrorKind},
     Parser,
 };
 
@@ -26,6 +26,22 @@ pub fn get_block_identifier(block: &Block) -> Option<String> {
 }
 
 impl<'a> Parser<'a> {
+    /// Parses the length of an array, which has to be an integer literal.
+    pub fn parse_array_length(&mut self) -> Result<usize, ParserError> {
+        let length = self.peek_or_eof()?;
+
+        match length.token {
+            Token::Literal(Literal::Int(i)) if i >= 0 => {
+                self.advance();
+                Ok(i as usize)
+            }
+            _ => Err(ParserError::new(
+                length.pos.clone(),
+                ParserErrorKind::Expected(&[Keyword::Literal]),
+            )),
+        }
+    }
+
     pub fn get_body(&mut self) -> Result<(Vec<Declaration>, Range<usize>), ParserError> {
         if let Some(open) = self.get(&[Keyword::BraceLeft]) {
             let mut content: Vec<Declaration> = Vec::new();
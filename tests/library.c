@@ -0,0 +1,45 @@
+#include <stdio.h>
+
+struct Point {
+	long x;
+	long y;
+};
+
+struct Box {
+	long left;
+	long top;
+	long width;
+	long height;
+};
+
+long add(long a, long b);
+long get_calls(void);
+long get_base(void);
+long sum(long *values, long count);
+long distance(struct Point point);
+struct Box grow(struct Box rect, long amount);
+long apply(long (*f)(long), long value);
+void greet(const char *name);
+
+static long triple(long value) {
+	return value * 3;
+}
+
+int main(void) {
+	long values[] = {1, 2, 3, 4};
+	struct Point point = {3, 4};
+	struct Box rect = {10, 20, 30, 40};
+
+	printf("%ld %ld\n", add(1, 2), add(40, 2));
+	printf("calls %ld\n", get_calls());
+	printf("base %ld\n", get_base());
+	printf("sum %ld\n", sum(values, 4));
+	printf("distance %ld\n", distance(point));
+
+	struct Box grown = grow(rect, 5);
+	printf("box %ld %ld %ld %ld\n", grown.left, grown.top, grown.width, grown.height);
+
+	printf("apply %ld\n", apply(triple, 7));
+	greet("c");
+	return 0;
+}